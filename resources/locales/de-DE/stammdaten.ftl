@@ -0,0 +1,225 @@
+## Allgemein
+
+create = Erstellen
+save = Speichern
+cancel = Abbrechen
+edit = Bearbeiten
+delete = Löschen
+copy = Kopieren
+remove = Entfernen
+pin = Anheften
+unpin = Lösen
+
+## Navigation
+
+nav-home = Basisdaten
+nav-bank-accounts = Konten
+nav-miscellaneous = Sonstiges
+nav-settings = Einstellungen
+nav-history = Verlauf
+
+## Profil
+
+profile-create-title = Profil erstellen
+profile-first-name = Vorname
+profile-last-name = Nachname
+profile-first-name-required = Bitte einen Vornamen eingeben.
+profile-last-name-required = Bitte einen Nachnamen eingeben.
+profile-import-title = Oder ein Profil importieren
+profile-import-hint = Stellt einen Export von einem anderen Gerät wieder her.
+profile-import = Importieren
+bundle-passphrase = Passphrase
+
+## Dokumente
+
+document-id-card = Personalausweis
+document-tax-id = Steuer-ID
+document-post-number = Postnummer
+document-social-security-number = Sozialversicherungsnummer
+document-create = { $name } erstellen
+
+expiry-expired =
+    { $days ->
+        [one] abgelaufen seit 1 Tag
+       *[other] abgelaufen seit { $days } Tagen
+    }
+expiry-today = läuft heute ab
+expiry-soon =
+    { $days ->
+        [one] läuft in 1 Tag ab
+       *[other] läuft in { $days } Tagen ab
+    }
+expiry-valid = gültig bis { $date }
+
+calendar-export = Kalender exportieren
+calendar-summary = { $name } läuft ab
+calendar-description = { $name } { $reference }
+calendar-alarm =
+    { $days ->
+        [one] { $name } läuft in 1 Tag ab
+       *[other] { $name } läuft in { $days } Tagen ab
+    }
+
+## Felder
+
+field-name = Name
+field-value = Wert
+field-label = Bezeichnung
+field-number = Nummer
+field-valid-until = Gültig bis
+field-post-number = Postnummer
+field-iban = IBAN
+field-category = Kategorie, z.B. { $examples }
+field-tags = Tags, durch Kommas getrennt
+
+field-kind-text = Text
+field-kind-number = Zahl
+field-kind-date = Datum
+field-kind-iban = IBAN
+field-kind-secret = Geheim
+
+## Konten und sonstige Einträge
+
+bank-account-create = Bankkonto erstellen
+bank-account-new = Neues Konto erstellen
+bank-account-copy-iban = IBAN kopieren
+
+item-create = Sonstigen Eintrag erstellen
+item-edit = Sonstigen Eintrag bearbeiten
+item-secret-yes = Geheim: ja
+item-secret-no = Geheim: nein
+category-none = Ohne Kategorie
+tags-all = Alle
+
+## Vorlagen
+
+templates = Vorlagen
+template-create = Vorlage erstellen
+template-edit = Vorlage bearbeiten
+template-add-field = Feld hinzufügen
+template-no-fields = Eine Vorlage benötigt mindestens ein Feld.
+entries = Einträge
+entry-new = Eintrag erstellen
+entry-create = { $name } erstellen
+
+## Löschen
+
+delete-title = Löschen?
+delete-document = Das Dokument „{ $name }“ wird gelöscht.
+delete-bank-account = Das Konto „{ $name }“ wird gelöscht.
+delete-item = Der Eintrag „{ $name }“ wird gelöscht.
+delete-template = Die Vorlage „{ $name }“ wird gelöscht.
+delete-template-with-entries = Die Vorlage „{ $name }“ wird gelöscht. Die Einträge der Vorlage bleiben erhalten.
+confirm-dont-ask-again = Bis zum Neustart nicht mehr fragen
+
+## Suche und Zwischenablage
+
+palette-search = Suchen
+palette-hint = ↑↓ auswählen, Enter kopieren, Esc schließen
+clipboard-copied = In die Zwischenablage kopiert
+clipboard-copied-until = In die Zwischenablage kopiert, wird in { $seconds } s geleert
+
+## Datum
+
+date-placeholder = TT.MM.JJJJ
+date-calendar = Kalender
+date-error-empty = Bitte ein Datum eingeben.
+date-error-invalid-format = Unbekanntes Datumsformat, z.B. 01.02.2030 verwenden.
+date-error-invalid-date = Dieses Datum existiert nicht.
+
+month-january = Januar
+month-february = Februar
+month-march = März
+month-april = April
+month-may = Mai
+month-june = Juni
+month-july = Juli
+month-august = August
+month-september = September
+month-october = Oktober
+month-november = November
+month-december = Dezember
+
+weekday-monday = Mo
+weekday-tuesday = Di
+weekday-wednesday = Mi
+weekday-thursday = Do
+weekday-friday = Fr
+weekday-saturday = Sa
+weekday-sunday = So
+
+## Konflikte
+
+conflict-title = Das Profil wurde geändert
+conflict-description = Das Profil wurde von einem anderen Programm geändert, z.B. auf einem anderen Gerät. Welche Werte sollen übernommen werden?
+conflict-mine = Hier
+conflict-theirs = Geändert
+conflict-empty = (leer)
+conflict-keep-mine = Meine behalten
+conflict-take-theirs = Geänderte übernehmen
+conflict-merge = Auswahl übernehmen
+
+## Verlauf
+
+history-empty = Noch keine Änderungen
+history-restore = Wiederherstellen
+
+merge-usage = Aufruf: stammdaten merge <Basis> <Lokal> <Entfernt> [<Ausgabe>]
+merge-conflicts =
+    { $count ->
+        [one] 1 Konflikt, der lokale Wert wurde behalten:
+       *[other] { $count } Konflikte, die lokalen Werte wurden behalten:
+    }
+merge-local = Lokal
+merge-remote = Entfernt
+merge-error-load = { $path } konnte nicht geladen werden: { $error }
+merge-error-save = { $path } konnte nicht gespeichert werden: { $error }
+
+## Einstellungen
+
+settings = Einstellungen
+settings-data-dir = Datenverzeichnis
+settings-data-dir-hint = Leer lassen, um das Standardverzeichnis zu verwenden.
+settings-language = Sprache
+settings-language-system = Systemsprache
+settings-date-format = Datumsformat
+settings-theme = Farbschema
+settings-theme-system = Wie das System
+settings-theme-dark = Dunkel
+settings-theme-light = Hell
+settings-theme-high-contrast = Hoher Kontrast
+settings-reminder-days = Erinnerungen in Tagen vor Ablauf, z.B. 90, 30, 7
+settings-clipboard-timeout = Zwischenablage leeren nach Sekunden, 0 behält kopierte Werte
+settings-saved = Gespeichert. Die Änderungen werden nach einem Neustart übernommen.
+settings-export = Daten exportieren
+settings-export-hint = Verschlüsselt das Profil und alle anderen Dateien im Datenverzeichnis mit der Passphrase, um sie auf einem anderen Gerät zu importieren.
+settings-export-button = Exportieren
+settings-exported = Exportiert.
+bundle-passphrase-too-short = Die Passphrase muss mindestens { $length } Zeichen haben.
+
+## Fehler
+
+error-required = Dies ist ein Pflichtfeld.
+error-invalid-date = Ungültiges Datum: { $value }
+error-invalid-number = Ungültige Zahl: { $value }
+error-invalid-iban = Ungültige IBAN: { $value }
+error-invalid-iban-check-digits = Die Prüfziffern der IBAN sind falsch: { $value }
+error-invalid-id-card-number = Ungültige Ausweisnummer: { $value }
+error-tax-id-length = Die Steuer-ID muss 11 Ziffern haben.
+error-invalid-tax-id = Ungültige Steuer-ID: { $value }
+error-post-number-length = Die Postnummer muss 9 Ziffern haben.
+error-invalid-post-number = Ungültige Postnummer: { $value }
+error-social-security-number-length = Die Sozialversicherungsnummer hat die falsche Länge: { $value }
+error-invalid-region-code = Ungültige Bereichsnummer: { $value }
+error-invalid-day-of-birth = Ungültiger Geburtstag: { $value }
+error-invalid-month-of-birth = Ungültiger Geburtsmonat: { $value }
+error-invalid-year-of-birth = Ungültiges Geburtsjahr: { $value }
+error-invalid-serial-number = Ungültige Seriennummer: { $value }
+error-invalid-check-digit = Ungültige Prüfziffer: { $value }
+error-invalid-lead-time = Ungültige Vorlaufzeit: { $value }
+bundle-error-not-a-bundle = Die Datei ist kein Export von Stammdaten.
+bundle-error-unsupported-version = Der Export wurde mit einer neueren Version von Stammdaten erstellt.
+bundle-error-decryption = Die Passphrase ist falsch oder der Export wurde verändert.
+bundle-error-invalid-contents = Der Export enthält kein gültiges Profil.
+bundle-error-read = Die Datei konnte nicht gelesen werden: { $error }
+bundle-error-write = Die Datei konnte nicht gespeichert werden: { $error }
@@ -0,0 +1,225 @@
+## General
+
+create = Create
+save = Save
+cancel = Cancel
+edit = Edit
+delete = Delete
+copy = Copy
+remove = Remove
+pin = Pin
+unpin = Unpin
+
+## Navigation
+
+nav-home = Basic data
+nav-bank-accounts = Accounts
+nav-miscellaneous = Miscellaneous
+nav-settings = Settings
+nav-history = History
+
+## Profile
+
+profile-create-title = Create profile
+profile-first-name = First name
+profile-last-name = Last name
+profile-first-name-required = Please enter a first name.
+profile-last-name-required = Please enter a last name.
+profile-import-title = Or import a profile
+profile-import-hint = Restores an export of another device.
+profile-import = Import
+bundle-passphrase = Passphrase
+
+## Documents
+
+document-id-card = ID card
+document-tax-id = Tax ID
+document-post-number = Post number
+document-social-security-number = Social security number
+document-create = Create { $name }
+
+expiry-expired =
+    { $days ->
+        [one] expired 1 day ago
+       *[other] expired { $days } days ago
+    }
+expiry-today = expires today
+expiry-soon =
+    { $days ->
+        [one] expires in 1 day
+       *[other] expires in { $days } days
+    }
+expiry-valid = valid until { $date }
+
+calendar-export = Export calendar
+calendar-summary = { $name } expires
+calendar-description = { $name } { $reference }
+calendar-alarm =
+    { $days ->
+        [one] { $name } expires in 1 day
+       *[other] { $name } expires in { $days } days
+    }
+
+## Fields
+
+field-name = Name
+field-value = Value
+field-label = Label
+field-number = Number
+field-valid-until = Valid until
+field-post-number = Post number
+field-iban = IBAN
+field-category = Category, e.g. { $examples }
+field-tags = Tags, separated by commas
+
+field-kind-text = Text
+field-kind-number = Number
+field-kind-date = Date
+field-kind-iban = IBAN
+field-kind-secret = Secret
+
+## Accounts and miscellaneous items
+
+bank-account-create = Create bank account
+bank-account-new = New account
+bank-account-copy-iban = Copy IBAN
+
+item-create = Create miscellaneous item
+item-edit = Edit miscellaneous item
+item-secret-yes = Secret: yes
+item-secret-no = Secret: no
+category-none = No category
+tags-all = All
+
+## Templates
+
+templates = Templates
+template-create = Create template
+template-edit = Edit template
+template-add-field = Add field
+template-no-fields = A template needs at least one field.
+entries = Entries
+entry-new = Create entry
+entry-create = Create { $name }
+
+## Deletion
+
+delete-title = Delete?
+delete-document = The document “{ $name }” will be deleted.
+delete-bank-account = The account “{ $name }” will be deleted.
+delete-item = The item “{ $name }” will be deleted.
+delete-template = The template “{ $name }” will be deleted.
+delete-template-with-entries = The template “{ $name }” will be deleted. The entries of the template are kept.
+confirm-dont-ask-again = Don't ask again until restart
+
+## Search and clipboard
+
+palette-search = Search
+palette-hint = ↑↓ select, Enter copy, Esc close
+clipboard-copied = Copied to the clipboard
+clipboard-copied-until = Copied to the clipboard, cleared in { $seconds } s
+
+## Dates
+
+date-placeholder = DD.MM.YYYY
+date-calendar = Calendar
+date-error-empty = Please enter a date.
+date-error-invalid-format = Unknown date format, use e.g. 01.02.2030.
+date-error-invalid-date = This date does not exist.
+
+month-january = January
+month-february = February
+month-march = March
+month-april = April
+month-may = May
+month-june = June
+month-july = July
+month-august = August
+month-september = September
+month-october = October
+month-november = November
+month-december = December
+
+weekday-monday = Mo
+weekday-tuesday = Tu
+weekday-wednesday = We
+weekday-thursday = Th
+weekday-friday = Fr
+weekday-saturday = Sa
+weekday-sunday = Su
+
+## Conflicts
+
+conflict-title = The profile was changed
+conflict-description = The profile was changed by another program, e.g. on another device. Which values should be kept?
+conflict-mine = Here
+conflict-theirs = Changed
+conflict-empty = (empty)
+conflict-keep-mine = Keep mine
+conflict-take-theirs = Take theirs
+conflict-merge = Take selection
+
+## History
+
+history-empty = No changes yet
+history-restore = Restore
+
+merge-usage = Usage: stammdaten merge <base> <local> <remote> [<output>]
+merge-conflicts =
+    { $count ->
+        [one] 1 conflict, the local value was kept:
+       *[other] { $count } conflicts, the local values were kept:
+    }
+merge-local = Local
+merge-remote = Remote
+merge-error-load = Could not load { $path }: { $error }
+merge-error-save = Could not save { $path }: { $error }
+
+## Settings
+
+settings = Settings
+settings-data-dir = Data directory
+settings-data-dir-hint = Leave empty to use the default directory.
+settings-language = Language
+settings-language-system = System language
+settings-date-format = Date format
+settings-theme = Theme
+settings-theme-system = Like the system
+settings-theme-dark = Dark
+settings-theme-light = Light
+settings-theme-high-contrast = High contrast
+settings-reminder-days = Reminders in days before expiry, e.g. 90, 30, 7
+settings-clipboard-timeout = Clear the clipboard after seconds, 0 keeps copied values
+settings-saved = Saved. The changes take effect after a restart.
+settings-export = Export data
+settings-export-hint = Encrypts the profile and all other files in the data directory with the passphrase, to import them on another device.
+settings-export-button = Export
+settings-exported = Exported.
+bundle-passphrase-too-short = The passphrase must have at least { $length } characters.
+
+## Errors
+
+error-required = This field is required.
+error-invalid-date = Invalid date: { $value }
+error-invalid-number = Invalid number: { $value }
+error-invalid-iban = Invalid IBAN: { $value }
+error-invalid-iban-check-digits = The check digits of the IBAN are wrong: { $value }
+error-invalid-id-card-number = Invalid ID card number: { $value }
+error-tax-id-length = The tax ID must have 11 digits.
+error-invalid-tax-id = Invalid tax ID: { $value }
+error-post-number-length = The post number must have 9 digits.
+error-invalid-post-number = Invalid post number: { $value }
+error-social-security-number-length = The social security number has the wrong length: { $value }
+error-invalid-region-code = Invalid region code: { $value }
+error-invalid-day-of-birth = Invalid day of birth: { $value }
+error-invalid-month-of-birth = Invalid month of birth: { $value }
+error-invalid-year-of-birth = Invalid year of birth: { $value }
+error-invalid-serial-number = Invalid serial number: { $value }
+error-invalid-check-digit = Invalid check digit: { $value }
+error-invalid-lead-time = Invalid lead time: { $value }
+bundle-error-not-a-bundle = The file is not an export of Stammdaten.
+bundle-error-unsupported-version = The export was created by a newer version of Stammdaten.
+bundle-error-decryption = The passphrase is wrong or the export was modified.
+bundle-error-invalid-contents = The export does not contain a valid profile.
+bundle-error-read = The file could not be read: { $error }
+bundle-error-write = The file could not be saved: { $error }
@@ -0,0 +1,98 @@
+//! Commands, which run without a window, e.g. as merge driver of a tool,
+//! which syncs the data directory.
+
+use crate::data::{merge, Conflict, Profile};
+use crate::i18n;
+use crate::state::field_label;
+use crate::widgets::MASK;
+use std::fs::File;
+use std::path::Path;
+
+/// `stammdaten merge <base> <local> <remote> [<output>]` merges the changes
+/// of two profiles. The result is written to `output`, by default to
+/// `local`. Conflicts keep the local value and are listed on stderr.
+///
+/// Returns the exit code: 1 if there were conflicts, 2 for errors.
+pub fn merge_profiles(args: &[String]) -> i32 {
+    let (base, local, remote, output) = match args {
+        [base, local, remote] => (base, local, remote, local),
+        [base, local, remote, output] => (base, local, remote, output),
+        _ => {
+            eprintln!("{}", i18n::text("merge-usage"));
+            return 2;
+        }
+    };
+
+    let profiles = [base, local, remote].map(|path| load_profile(path));
+    let (base, local, remote) = match profiles {
+        [Ok(base), Ok(local), Ok(remote)] => (base, local, remote),
+        profiles => {
+            for error in profiles.into_iter().filter_map(Result::err) {
+                eprintln!("{}", error);
+            }
+            return 2;
+        }
+    };
+
+    let merge = merge(&base, &local, &remote);
+    if let Err(error) = save_profile(&merge.profile, output) {
+        eprintln!("{}", error);
+        return 2;
+    }
+
+    if merge.conflicts.is_empty() {
+        return 0;
+    }
+
+    eprintln!(
+        "{}",
+        i18n::text_with(
+            "merge-conflicts",
+            &[("count", merge.conflicts.len().into())]
+        )
+    );
+    for conflict in &merge.conflicts {
+        eprintln!("{}", describe_conflict(conflict));
+    }
+    1
+}
+
+fn load_profile(path: &str) -> Result<Profile, String> {
+    File::open(path)
+        .and_then(|mut file| Profile::load_from_file(&mut file))
+        .map_err(|error| {
+            i18n::text_with(
+                "merge-error-load",
+                &[("path", path.into()), ("error", error.to_string().into())],
+            )
+        })
+}
+
+fn save_profile(profile: &Profile, path: &str) -> Result<(), String> {
+    File::create(Path::new(path))
+        .and_then(|mut file| profile.save_to_file(&mut file))
+        .map_err(|error| {
+            i18n::text_with(
+                "merge-error-save",
+                &[("path", path.into()), ("error", error.to_string().into())],
+            )
+        })
+}
+
+/// The field with both values, secret values are masked.
+fn describe_conflict(conflict: &Conflict) -> String {
+    let describe = |value: &Option<String>| match (value, conflict.is_secret) {
+        (None, _) => i18n::text("conflict-empty"),
+        (Some(_), true) => String::from(MASK),
+        (Some(value), false) => value.clone(),
+    };
+
+    format!(
+        "  {}\n    {}: {}\n    {}: {}",
+        field_label(&conflict.field),
+        i18n::text("merge-local"),
+        describe(&conflict.local),
+        i18n::text("merge-remote"),
+        describe(&conflict.remote),
+    )
+}
@@ -0,0 +1,157 @@
+//! Copy values to the clipboard and clear them again after a timeout, so
+//! that IBANs and ids don't stay on the clipboard forever.
+
+use druid::Application;
+use std::time::{Duration, Instant};
+
+pub trait Clipboard {
+    fn get_string(&self) -> Option<String>;
+    fn put_string(&mut self, value: &str);
+    fn clear(&mut self);
+}
+
+/// The clipboard of the operating system.
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn get_string(&self) -> Option<String> {
+        Application::global().clipboard().get_string()
+    }
+
+    fn put_string(&mut self, value: &str) {
+        Application::global().clipboard().put_string(value);
+    }
+
+    fn clear(&mut self) {
+        Application::global().clipboard().put_string("");
+    }
+}
+
+pub struct ClipboardManager<C: Clipboard> {
+    clipboard: C,
+    /// The copied value and the time after which it is cleared.
+    pending: Option<(String, Instant)>,
+}
+
+impl<C: Clipboard> ClipboardManager<C> {
+    pub fn new(clipboard: C) -> ClipboardManager<C> {
+        ClipboardManager {
+            clipboard,
+            pending: None,
+        }
+    }
+
+    /// Copy `value` and clear it after `timeout`, if there is one.
+    pub fn copy(&mut self, value: &str, timeout: Option<Duration>, now: Instant) {
+        self.clipboard.put_string(value);
+        self.pending = timeout.map(|timeout| (String::from(value), now + timeout));
+    }
+
+    /// Time until the copied value is cleared.
+    pub fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.pending
+            .as_ref()
+            .map(|(_, deadline)| deadline.saturating_duration_since(now))
+    }
+
+    /// Clear the clipboard, once the timeout has passed. The clipboard is
+    /// only cleared if it still contains the copied value, to not remove
+    /// anything the user copied in the meantime. Returns whether a value is
+    /// still pending.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if let Some((value, deadline)) = &self.pending {
+            if now >= *deadline {
+                if self.clipboard.get_string().as_ref() == Some(value) {
+                    self.clipboard.clear();
+                }
+                self.pending = None;
+            }
+        }
+
+        self.pending.is_some()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct FakeClipboard {
+        value: Option<String>,
+    }
+
+    impl Clipboard for FakeClipboard {
+        fn get_string(&self) -> Option<String> {
+            self.value.clone()
+        }
+
+        fn put_string(&mut self, value: &str) {
+            self.value = Some(String::from(value));
+        }
+
+        fn clear(&mut self) {
+            self.value = None;
+        }
+    }
+
+    #[test]
+    fn should_clear_the_clipboard_after_the_timeout() {
+        let mut manager = ClipboardManager::new(FakeClipboard::default());
+        let now = Instant::now();
+
+        manager.copy("DE89", Some(Duration::from_secs(30)), now);
+
+        assert!(manager.tick(now + Duration::from_secs(29)));
+        assert_eq!(manager.clipboard.get_string(), Some(String::from("DE89")));
+        assert_eq!(
+            manager.remaining(now + Duration::from_secs(20)),
+            Some(Duration::from_secs(10))
+        );
+        assert!(!manager.tick(now + Duration::from_secs(30)));
+        assert_eq!(manager.clipboard.get_string(), None);
+    }
+
+    #[test]
+    fn should_keep_values_copied_by_the_user_in_the_meantime() {
+        let mut manager = ClipboardManager::new(FakeClipboard::default());
+        let now = Instant::now();
+
+        manager.copy("DE89", Some(Duration::from_secs(30)), now);
+        manager.clipboard.put_string("something else");
+
+        assert!(!manager.tick(now + Duration::from_secs(30)));
+        assert_eq!(
+            manager.clipboard.get_string(),
+            Some(String::from("something else"))
+        );
+    }
+
+    #[test]
+    fn should_not_clear_the_clipboard_without_timeout() {
+        let mut manager = ClipboardManager::new(FakeClipboard::default());
+        let now = Instant::now();
+
+        manager.copy("DE89", None, now);
+
+        assert!(!manager.tick(now + Duration::from_secs(3600)));
+        assert_eq!(manager.remaining(now), None);
+        assert_eq!(manager.clipboard.get_string(), Some(String::from("DE89")));
+    }
+
+    #[test]
+    fn should_restart_the_timeout_when_copying_again() {
+        let mut manager = ClipboardManager::new(FakeClipboard::default());
+        let now = Instant::now();
+
+        manager.copy("DE89", Some(Duration::from_secs(30)), now);
+        manager.copy(
+            "1234",
+            Some(Duration::from_secs(30)),
+            now + Duration::from_secs(20),
+        );
+
+        assert!(manager.tick(now + Duration::from_secs(30)));
+        assert_eq!(manager.clipboard.get_string(), Some(String::from("1234")));
+    }
+}
@@ -0,0 +1,387 @@
+//! Encrypted bundle of all files in the data directory, to move the profile
+//! to another machine.
+//!
+//! A bundle starts with `MAGIC` and the format version, followed by the
+//! salt and the nonce. The rest are the files and the version of the app as
+//! JSON, encrypted with ChaCha20-Poly1305 and a key, which is derived from
+//! the passphrase with Argon2id. The header is authenticated as associated
+//! data, so any change of the bundle fails the decryption like a wrong
+//! passphrase.
+
+use super::profile::Profile;
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str;
+
+/// Extension of bundle files.
+pub const BUNDLE_EXTENSION: &str = "stammdaten";
+/// Passphrases have to be at least this long.
+pub const MIN_PASSPHRASE_LENGTH: usize = 8;
+
+const MAGIC: &[u8; 10] = b"STAMMDATEN";
+/// Version of the format, which is increased for incompatible changes,
+/// e.g. of the key derivation.
+const FORMAT_VERSION: u8 = 1;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+const HEADER_LENGTH: usize = MAGIC.len() + 1 + SALT_LENGTH + NONCE_LENGTH;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BundleFile {
+    /// Path relative to the data directory with `/` as separator.
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Contents {
+    /// Version of the app, which created the bundle.
+    app_version: String,
+    files: Vec<BundleFile>,
+}
+
+/// Reasons why a bundle cannot be imported. Like `ErrorCode`, they are
+/// codes for the messages in the language of the user.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BundleError {
+    NotABundle,
+    /// The bundle was created by a newer version of the app.
+    UnsupportedVersion,
+    /// The passphrase is wrong or the bundle was modified.
+    Decryption,
+    /// The bundle does not contain a valid profile or has invalid paths.
+    InvalidContents,
+}
+
+impl BundleError {
+    pub const ALL: [BundleError; 4] = [
+        BundleError::NotABundle,
+        BundleError::UnsupportedVersion,
+        BundleError::Decryption,
+        BundleError::InvalidContents,
+    ];
+
+    /// Id of the message in the localization resources.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            BundleError::NotABundle => "bundle-error-not-a-bundle",
+            BundleError::UnsupportedVersion => "bundle-error-unsupported-version",
+            BundleError::Decryption => "bundle-error-decryption",
+            BundleError::InvalidContents => "bundle-error-invalid-contents",
+        }
+    }
+}
+
+/// Only used for logs, the UI shows the localized message.
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message_id())
+    }
+}
+
+impl Error for BundleError {}
+
+/// Encrypt the files with the passphrase.
+pub fn create_bundle(files: Vec<BundleFile>, passphrase: &str) -> Vec<u8> {
+    let contents = Contents {
+        app_version: String::from(env!("CARGO_PKG_VERSION")),
+        files,
+    };
+
+    seal(&contents, passphrase, FORMAT_VERSION)
+}
+
+/// Decrypt the files of a bundle and verify, that it was not modified and
+/// can be read by this version of the app.
+pub fn open_bundle(bundle: &[u8], passphrase: &str) -> Result<Vec<BundleFile>, BundleError> {
+    if bundle.len() < HEADER_LENGTH || !bundle.starts_with(MAGIC) {
+        return Err(BundleError::NotABundle);
+    }
+    if bundle[MAGIC.len()] != FORMAT_VERSION {
+        return Err(BundleError::UnsupportedVersion);
+    }
+
+    let (header, ciphertext) = bundle.split_at(HEADER_LENGTH);
+    let salt = &header[MAGIC.len() + 1..MAGIC.len() + 1 + SALT_LENGTH];
+    let nonce = Nonce::from_slice(&header[HEADER_LENGTH - NONCE_LENGTH..]);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt));
+    let plaintext = cipher
+        .decrypt(
+            nonce,
+            Payload {
+                msg: ciphertext,
+                aad: header,
+            },
+        )
+        .map_err(|_| BundleError::Decryption)?;
+
+    let contents: Contents =
+        serde_json::from_slice(&plaintext).map_err(|_| BundleError::InvalidContents)?;
+    if is_newer_version(&contents.app_version, env!("CARGO_PKG_VERSION")) {
+        return Err(BundleError::UnsupportedVersion);
+    }
+    if !contents
+        .files
+        .iter()
+        .all(|file| is_relative_path(&file.name))
+    {
+        return Err(BundleError::InvalidContents);
+    }
+
+    Ok(contents.files)
+}
+
+/// The profile in the file `name` of a bundle.
+pub fn bundled_profile(files: &[BundleFile], name: &str) -> Result<Profile, BundleError> {
+    files
+        .iter()
+        .find(|file| file.name == name)
+        .and_then(|file| str::from_utf8(&file.data).ok())
+        .and_then(|data| data.parse().ok())
+        .ok_or(BundleError::InvalidContents)
+}
+
+/// All files in `dir` and its subdirectories.
+pub fn read_directory(dir: &Path) -> io::Result<Vec<BundleFile>> {
+    let mut files = Vec::new();
+    read_directory_into(dir, "", &mut files)?;
+    files.sort_by(|file, other| file.name.cmp(&other.name));
+
+    Ok(files)
+}
+
+fn read_directory_into(dir: &Path, prefix: &str, files: &mut Vec<BundleFile>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+        if entry.file_type()?.is_dir() {
+            read_directory_into(&entry.path(), &format!("{}/", name), files)?;
+        } else {
+            files.push(BundleFile {
+                name,
+                data: fs::read(entry.path())?,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the files to `dir`. Existing files are overwritten.
+pub fn write_directory(dir: &Path, files: &[BundleFile]) -> io::Result<()> {
+    for file in files {
+        let path = dir.join(&file.name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &file.data)?;
+    }
+
+    Ok(())
+}
+
+fn seal(contents: &Contents, passphrase: &str, version: u8) -> Vec<u8> {
+    let mut salt = [0; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let mut bundle = Vec::with_capacity(HEADER_LENGTH);
+    bundle.extend_from_slice(MAGIC);
+    bundle.push(version);
+    bundle.extend_from_slice(&salt);
+    bundle.extend_from_slice(&nonce);
+
+    let plaintext = serde_json::to_vec(contents).expect("Could not serialize the bundle");
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt));
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &plaintext,
+                aad: &bundle,
+            },
+        )
+        .expect("Could not encrypt the bundle");
+    bundle.extend(ciphertext);
+
+    bundle
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut_slice())
+        .expect("Could not derive the key");
+
+    key
+}
+
+/// Whether `version` is newer than `current`, e.g. `0.3.0` than `0.2.1`.
+fn is_newer_version(version: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+
+    parse(version) > parse(current)
+}
+
+/// Whether the path stays inside the data directory.
+fn is_relative_path(name: &str) -> bool {
+    name.split('/')
+        .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains(['\\', ':']))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn files() -> Vec<BundleFile> {
+        let profile = Profile::new("Test".into(), "Name".into());
+
+        vec![
+            BundleFile {
+                name: "profile.json".into(),
+                data: serde_json::to_vec(&profile).unwrap(),
+            },
+            BundleFile {
+                name: "history.jsonl".into(),
+                data: b"{}\n".to_vec(),
+            },
+        ]
+    }
+
+    #[test]
+    fn should_open_a_created_bundle() {
+        let bundle = create_bundle(files(), "correct horse");
+        let opened = open_bundle(&bundle, "correct horse").unwrap();
+
+        assert_eq!(opened, files());
+        assert_eq!(
+            bundled_profile(&opened, "profile.json").unwrap(),
+            Profile::new("Test".into(), "Name".into())
+        );
+        assert_eq!(
+            bundled_profile(&opened, "history.jsonl"),
+            Err(BundleError::InvalidContents)
+        );
+    }
+
+    #[test]
+    fn should_reject_a_wrong_passphrase_and_modified_bundles() {
+        let bundle = create_bundle(files(), "correct horse");
+        let modified = |index: usize| {
+            let mut bundle = bundle.clone();
+            bundle[index] ^= 1;
+            bundle
+        };
+
+        assert_eq!(
+            open_bundle(&bundle, "wrong horse"),
+            Err(BundleError::Decryption)
+        );
+        assert_eq!(
+            open_bundle(&modified(bundle.len() - 1), "correct horse"),
+            Err(BundleError::Decryption)
+        );
+        assert_eq!(
+            open_bundle(&modified(MAGIC.len() + 1), "correct horse"),
+            Err(BundleError::Decryption)
+        );
+        assert_eq!(
+            open_bundle(&modified(0), "correct horse"),
+            Err(BundleError::NotABundle)
+        );
+        assert_eq!(
+            open_bundle(&bundle[..HEADER_LENGTH - 1], "correct horse"),
+            Err(BundleError::NotABundle)
+        );
+    }
+
+    #[test]
+    fn should_reject_bundles_of_newer_versions() {
+        let newer_format = seal(
+            &Contents {
+                app_version: env!("CARGO_PKG_VERSION").into(),
+                files: files(),
+            },
+            "correct horse",
+            FORMAT_VERSION + 1,
+        );
+        let newer_app = seal(
+            &Contents {
+                app_version: "999.0.0".into(),
+                files: files(),
+            },
+            "correct horse",
+            FORMAT_VERSION,
+        );
+
+        assert_eq!(
+            open_bundle(&newer_format, "correct horse"),
+            Err(BundleError::UnsupportedVersion)
+        );
+        assert_eq!(
+            open_bundle(&newer_app, "correct horse"),
+            Err(BundleError::UnsupportedVersion)
+        );
+        assert!(is_newer_version("0.10.0", "0.9.1"));
+        assert!(!is_newer_version("0.2.0", "0.2.0"));
+    }
+
+    #[test]
+    fn should_only_write_files_inside_the_data_directory() {
+        let escaping = seal(
+            &Contents {
+                app_version: env!("CARGO_PKG_VERSION").into(),
+                files: vec![BundleFile {
+                    name: "../profile.json".into(),
+                    data: Vec::new(),
+                }],
+            },
+            "correct horse",
+            FORMAT_VERSION,
+        );
+
+        assert_eq!(
+            open_bundle(&escaping, "correct horse"),
+            Err(BundleError::InvalidContents)
+        );
+        assert!(is_relative_path("backups/profile.json"));
+        assert!(!is_relative_path("/etc/passwd"));
+        assert!(!is_relative_path("C:\\profile.json"));
+    }
+
+    #[test]
+    fn should_read_and_write_the_data_directory() {
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir(source.path().join("backups")).unwrap();
+        fs::write(source.path().join("profile.json"), "{}").unwrap();
+        fs::write(source.path().join("backups").join("old.json"), "[]").unwrap();
+
+        let files = read_directory(source.path()).unwrap();
+        let target = tempfile::tempdir().unwrap();
+        write_directory(target.path(), &files).unwrap();
+
+        assert_eq!(
+            files
+                .iter()
+                .map(|file| file.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["backups/old.json", "profile.json"]
+        );
+        assert_eq!(read_directory(target.path()).unwrap(), files);
+    }
+}
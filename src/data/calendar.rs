@@ -0,0 +1,178 @@
+//! Export of document expiries in the iCalendar format (RFC 5545).
+
+use super::expiry::Expiry;
+use chrono::prelude::*;
+use chrono::Duration;
+
+const PRODUCT_ID: &str = "-//flxbe//Stammdaten//DE";
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Texts of the events in the language of the user.
+pub trait EventTexts {
+    /// Summary of the event, e.g. `Personalausweis läuft ab`.
+    fn summary(&self, expiry: &Expiry) -> String;
+
+    /// Description of the event, e.g. `Personalausweis 48328FGW9`.
+    fn description(&self, expiry: &Expiry) -> String;
+
+    /// Description of the alarm `days` before the expiry.
+    fn alarm(&self, expiry: &Expiry, days: i64) -> String;
+}
+
+/// Create a calendar with one all-day event per expiry and one alarm per
+/// lead time.
+pub fn to_icalendar(
+    expiries: &[Expiry],
+    lead_times: &[Duration],
+    texts: &dyn EventTexts,
+    now: DateTime<Utc>,
+) -> String {
+    let mut lines = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        format!("PRODID:{}", PRODUCT_ID),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("METHOD:PUBLISH"),
+    ];
+
+    for expiry in expiries {
+        let date = expiry.valid_until;
+
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!("UID:{}", event_uid(expiry)));
+        lines.push(format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        lines.push(format!(
+            "DTEND;VALUE=DATE:{}",
+            (date + Duration::days(1)).format("%Y%m%d")
+        ));
+        lines.push(format!("SUMMARY:{}", escape_text(&texts.summary(expiry))));
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            escape_text(&texts.description(expiry))
+        ));
+
+        for lead_time in lead_times {
+            lines.push(String::from("BEGIN:VALARM"));
+            lines.push(String::from("ACTION:DISPLAY"));
+            lines.push(format!(
+                "DESCRIPTION:{}",
+                escape_text(&texts.alarm(expiry, lead_time.num_days()))
+            ));
+            lines.push(format!("TRIGGER:-P{}D", lead_time.num_days()));
+            lines.push(String::from("END:VALARM"));
+        }
+
+        lines.push(String::from("END:VEVENT"));
+    }
+
+    lines.push(String::from("END:VCALENDAR"));
+
+    lines
+        .iter()
+        .map(|line| fold_line(line))
+        .map(|line| line + "\r\n")
+        .collect()
+}
+
+/// Create a UID, which stays the same between exports, so that calendar
+/// subscriptions update the existing event instead of adding a new one.
+fn event_uid(expiry: &Expiry) -> String {
+    let name: String = format!("{}-{}", expiry.title, expiry.reference)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+
+    format!("{}@stammdaten", name.to_lowercase())
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Split lines longer than 75 octets into multiple lines, where each
+/// continuation line starts with a single space.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_length = 0;
+
+    for c in line.chars() {
+        if line_length + c.len_utf8() > MAX_LINE_LENGTH {
+            folded.push_str("\r\n ");
+            line_length = 1;
+        }
+
+        folded.push(c);
+        line_length += c.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Texts;
+
+    impl EventTexts for Texts {
+        fn summary(&self, expiry: &Expiry) -> String {
+            format!("{} läuft ab", expiry.title)
+        }
+
+        fn description(&self, expiry: &Expiry) -> String {
+            format!("{} {}", expiry.title, expiry.reference)
+        }
+
+        fn alarm(&self, expiry: &Expiry, days: i64) -> String {
+            format!("{} läuft in {} Tagen ab", expiry.title, days)
+        }
+    }
+
+    #[test]
+    fn should_create_an_event_with_alarms_for_each_expiry() {
+        let expiry = Expiry {
+            title: "Personalausweis".into(),
+            name: "document-id-card",
+            reference: "48328FGW9".into(),
+            valid_until: NaiveDate::from_ymd(2031, 3, 1),
+        };
+        let now = Utc.ymd(2022, 12, 16).and_hms(12, 0, 0);
+
+        let calendar = to_icalendar(
+            &[expiry],
+            &[Duration::days(30), Duration::days(7)],
+            &Texts,
+            now,
+        );
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 1);
+        assert_eq!(calendar.matches("BEGIN:VALARM").count(), 2);
+        assert!(calendar.contains("UID:personalausweis-48328fgw9@stammdaten\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20310301\r\n"));
+        assert!(calendar.contains("DTEND;VALUE=DATE:20310302\r\n"));
+        assert!(calendar.contains("TRIGGER:-P30D\r\n"));
+        assert!(calendar.contains("TRIGGER:-P7D\r\n"));
+        assert!(calendar.contains("SUMMARY:Personalausweis läuft ab\r\n"));
+    }
+
+    #[test]
+    fn should_escape_special_characters() {
+        assert_eq!(escape_text("a,b;c\\d"), "a\\,b\\;c\\\\d");
+    }
+
+    #[test]
+    fn should_fold_long_lines() {
+        let line = "X".repeat(100);
+
+        let folded = fold_line(&line);
+
+        assert_eq!(folded, format!("{}\r\n {}", "X".repeat(75), "X".repeat(25)));
+    }
+}
@@ -0,0 +1,346 @@
+//! Registry of the document types, which can be stored in a profile.
+//!
+//! A document is a list of string values, one per field of its type. The
+//! built-in types are stored in their typed fields of the profile, all
+//! other types in `Profile::other_documents`. To add a new type, implement
+//! `DocumentType` and add it to `DOCUMENT_TYPES`.
+//!
+//! Names and labels are ids of localized messages, e.g. `document-tax-id`.
+//! A text without a message, e.g. `Bibliotheksausweis`, is shown as is.
+
+use super::error::{DataError, ErrorCode};
+use super::expiry::{Expiry, ExpiryStatus};
+use super::id_card::IdCardType;
+use super::post_number::PostNumberType;
+use super::profile::Profile;
+use super::social_security_number::SocialSecurityNumberType;
+use super::tax_id::TaxIdType;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+static DOCUMENT_TYPES: [&dyn DocumentType; 4] = [
+    &IdCardType,
+    &SocialSecurityNumberType,
+    &TaxIdType,
+    &PostNumberType,
+];
+
+/// All registered document types in the order they are shown.
+pub fn document_types() -> &'static [&'static dyn DocumentType] {
+    &DOCUMENT_TYPES
+}
+
+/// Sort the document types like they are shown on the home page: dated
+/// documents of `profile` by their `ExpiryStatus` and expiration date,
+/// followed by all other types in their previous order.
+pub fn sort_by_expiry(
+    document_types: &mut [&'static dyn DocumentType],
+    profile: &Profile,
+    threshold: Duration,
+    today: NaiveDate,
+) {
+    document_types.sort_by_key(|document_type| {
+        let expiry: Option<(ExpiryStatus, NaiveDate)> = document_type
+            .read(profile)
+            .and_then(|document| document_type.expiry(&document))
+            .map(|expiry| (expiry.status(threshold, today), expiry.valid_until));
+
+        (expiry.is_none(), expiry)
+    });
+}
+
+pub fn document_type(id: &str) -> Option<&'static dyn DocumentType> {
+    document_types()
+        .iter()
+        .find(|document_type| document_type.id() == id)
+        .copied()
+}
+
+type Validate = fn(&str) -> Result<(), DataError>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FieldKind {
+    Text,
+    /// Dates are stored as `2030-02-01`.
+    Date,
+}
+
+#[derive(Clone, Copy)]
+pub struct FieldSpec {
+    pub label: &'static str,
+    pub kind: FieldKind,
+    pub required: bool,
+    /// Validation of a non-empty value.
+    pub validate: Option<Validate>,
+}
+
+impl FieldSpec {
+    pub fn text(label: &'static str) -> FieldSpec {
+        FieldSpec {
+            label,
+            kind: FieldKind::Text,
+            required: true,
+            validate: None,
+        }
+    }
+
+    pub fn date(label: &'static str) -> FieldSpec {
+        FieldSpec {
+            label,
+            kind: FieldKind::Date,
+            required: true,
+            validate: None,
+        }
+    }
+
+    pub fn optional(mut self) -> FieldSpec {
+        self.required = false;
+        self
+    }
+
+    pub fn validate(mut self, validate: Validate) -> FieldSpec {
+        self.validate = Some(validate);
+        self
+    }
+
+    fn check(&self, value: &str) -> Result<(), DataError> {
+        if value.is_empty() {
+            return match self.required {
+                true => Err(DataError::new(ErrorCode::Required, self.label)),
+                false => Ok(()),
+            };
+        }
+
+        if self.kind == FieldKind::Date {
+            NaiveDate::from_str(value)
+                .map_err(|_| DataError::new(ErrorCode::InvalidDate, value))?;
+        }
+
+        match self.validate {
+            Some(validate) => validate(value),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Document {
+    #[serde(rename = "type")]
+    pub type_id: String,
+    pub values: Vec<String>,
+}
+
+impl Document {
+    pub fn new(type_id: &str, values: Vec<String>) -> Document {
+        Document {
+            type_id: String::from(type_id),
+            values,
+        }
+    }
+
+    /// The value of the field at `index` or an empty string.
+    pub fn value(&self, index: usize) -> &str {
+        self.values.get(index).map(String::as_str).unwrap_or("")
+    }
+
+    /// The registered type of this document, if any.
+    pub fn document_type(&self) -> Option<&'static dyn DocumentType> {
+        document_type(&self.type_id)
+    }
+
+    pub fn expiry(&self) -> Option<Expiry> {
+        self.document_type()?.expiry(self)
+    }
+}
+
+/// A kind of document, e.g. the tax id. Only the identification and the
+/// fields are required, all other methods have defaults for documents,
+/// which are stored in `Profile::other_documents`.
+pub trait DocumentType: Sync {
+    /// Stable identifier, which is stored with the document.
+    fn id(&self) -> &'static str;
+
+    /// Name of the type, e.g. `document-tax-id`.
+    fn name(&self) -> &'static str;
+
+    fn fields(&self) -> Vec<FieldSpec>;
+
+    /// Create a document from the entered values, one per field.
+    fn parse(&self, values: &[String]) -> Result<Document, DataError> {
+        let fields = self.fields();
+        let values: Vec<String> = (0..fields.len())
+            .map(|index| values.get(index).map(|value| value.trim()).unwrap_or(""))
+            .map(String::from)
+            .collect();
+
+        for (field, value) in fields.iter().zip(values.iter()) {
+            field.check(value)?;
+        }
+
+        Ok(Document::new(self.id(), values))
+    }
+
+    /// The value shown for the document.
+    fn format(&self, document: &Document) -> String {
+        document
+            .values
+            .iter()
+            .filter(|value| !value.is_empty())
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    /// Whether the value is masked in the UI.
+    fn is_secret(&self) -> bool {
+        false
+    }
+
+    /// The value copied to the clipboard.
+    fn copy_value(&self, document: &Document) -> String {
+        String::from(document.value(0))
+    }
+
+    fn expiry(&self, _document: &Document) -> Option<Expiry> {
+        None
+    }
+
+    fn read(&self, profile: &Profile) -> Option<Document> {
+        profile
+            .other_documents
+            .iter()
+            .find(|document| document.type_id == self.id())
+            .cloned()
+    }
+
+    fn write(&self, profile: &mut Profile, document: Option<Document>) {
+        profile
+            .other_documents
+            .retain(|document| document.type_id != self.id());
+        profile.other_documents.extend(document);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct LibraryCardType;
+
+    impl DocumentType for LibraryCardType {
+        fn id(&self) -> &'static str {
+            "library_card"
+        }
+
+        fn name(&self) -> &'static str {
+            "Bibliotheksausweis"
+        }
+
+        fn fields(&self) -> Vec<FieldSpec> {
+            vec![
+                FieldSpec::text("Nummer"),
+                FieldSpec::date("Gültig bis").optional(),
+            ]
+        }
+
+        fn expiry(&self, document: &Document) -> Option<Expiry> {
+            Some(Expiry {
+                title: self.name().into(),
+                name: self.name(),
+                reference: document.value(0).into(),
+                valid_until: NaiveDate::from_str(document.value(1)).ok()?,
+            })
+        }
+    }
+
+    #[test]
+    fn should_find_the_registered_types() {
+        assert_eq!(document_type("tax_id").unwrap().name(), "document-tax-id");
+        assert!(document_type("library_card").is_none());
+    }
+
+    #[test]
+    fn should_validate_the_fields_of_custom_types() {
+        let document = LibraryCardType
+            .parse(&[String::from(" 42 "), String::new()])
+            .unwrap();
+
+        assert_eq!(document.values, vec!["42", ""]);
+        assert_eq!(LibraryCardType.format(&document), "42");
+        assert!(LibraryCardType.parse(&[String::new()]).is_err());
+        assert!(LibraryCardType
+            .parse(&[String::from("42"), String::from("1.2.30")])
+            .is_err());
+    }
+
+    #[test]
+    fn should_sort_the_most_urgent_documents_first() {
+        let today = NaiveDate::from_ymd(2030, 2, 1);
+        let id_card = IdCardType
+            .parse(&[String::from("09321CFG"), String::from("2030-02-20")])
+            .unwrap();
+        let mut library_card = LibraryCardType
+            .parse(&[String::from("42"), String::from("2031-01-01")])
+            .unwrap();
+        let mut profile = Profile::new("Test".into(), "Name".into());
+        profile.set_documents(vec![id_card.clone(), library_card.clone()]);
+
+        let mut document_types: Vec<&'static dyn DocumentType> =
+            vec![&TaxIdType, &LibraryCardType, &PostNumberType, &IdCardType];
+        let ids = |document_types: &[&'static dyn DocumentType]| -> Vec<&str> {
+            document_types
+                .iter()
+                .map(|document_type| document_type.id())
+                .collect()
+        };
+
+        sort_by_expiry(&mut document_types, &profile, Duration::days(30), today);
+        assert_eq!(
+            ids(&document_types),
+            vec!["id_card", "library_card", "tax_id", "post_number"]
+        );
+
+        library_card.values[1] = String::from("2030-01-01");
+        profile.set_documents(vec![id_card, library_card]);
+        sort_by_expiry(&mut document_types, &profile, Duration::days(30), today);
+        assert_eq!(
+            ids(&document_types),
+            vec!["library_card", "id_card", "tax_id", "post_number"]
+        );
+    }
+
+    #[test]
+    fn should_store_custom_types_in_the_other_documents() {
+        let mut profile = Profile::new("Test".into(), "Name".into());
+        let document = Document::new("library_card", vec!["42".into()]);
+
+        LibraryCardType.write(&mut profile, Some(document.clone()));
+
+        assert_eq!(profile.other_documents, vec![document.clone()]);
+        assert_eq!(LibraryCardType.read(&profile), Some(document));
+    }
+
+    #[test]
+    fn should_store_built_in_types_in_their_fields() {
+        let mut profile = Profile::new("Test".into(), "Name".into());
+        let tax_id = document_type("tax_id").unwrap();
+        let document = tax_id.parse(&[String::from("12 123 456 789")]).unwrap();
+
+        profile.set_documents(vec![document.clone()]);
+
+        assert!(profile.tax_id.is_some());
+        assert!(profile.other_documents.is_empty());
+        assert_eq!(profile.documents(), vec![document]);
+    }
+
+    #[test]
+    fn should_keep_documents_of_unknown_types() {
+        let mut profile = Profile::new("Test".into(), "Name".into());
+        let document = Document::new("library_card", vec!["42".into()]);
+
+        profile.set_documents(vec![document.clone()]);
+
+        assert_eq!(profile.documents(), vec![document]);
+    }
+}
@@ -0,0 +1,103 @@
+//! Errors of the data layer. They are codes instead of formatted messages,
+//! so that the UI can show them in the language of the user.
+
+use std::error::Error;
+use std::fmt;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ErrorCode {
+    /// A required value is empty.
+    Required,
+    InvalidDate,
+    InvalidNumber,
+    InvalidIban,
+    InvalidIbanCheckDigits,
+    InvalidIdCardNumber,
+    /// The tax id does not have 11 digits.
+    TaxIdLength,
+    InvalidTaxId,
+    /// The post number does not have 9 digits.
+    PostNumberLength,
+    InvalidPostNumber,
+    SocialSecurityNumberLength,
+    InvalidRegionCode,
+    InvalidDayOfBirth,
+    InvalidMonthOfBirth,
+    InvalidYearOfBirth,
+    InvalidSerialNumber,
+    InvalidCheckDigit,
+    InvalidLeadTime,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 18] = [
+        ErrorCode::Required,
+        ErrorCode::InvalidDate,
+        ErrorCode::InvalidNumber,
+        ErrorCode::InvalidIban,
+        ErrorCode::InvalidIbanCheckDigits,
+        ErrorCode::InvalidIdCardNumber,
+        ErrorCode::TaxIdLength,
+        ErrorCode::InvalidTaxId,
+        ErrorCode::PostNumberLength,
+        ErrorCode::InvalidPostNumber,
+        ErrorCode::SocialSecurityNumberLength,
+        ErrorCode::InvalidRegionCode,
+        ErrorCode::InvalidDayOfBirth,
+        ErrorCode::InvalidMonthOfBirth,
+        ErrorCode::InvalidYearOfBirth,
+        ErrorCode::InvalidSerialNumber,
+        ErrorCode::InvalidCheckDigit,
+        ErrorCode::InvalidLeadTime,
+    ];
+
+    /// Id of the message in the localization resources.
+    pub fn message_id(self) -> &'static str {
+        match self {
+            ErrorCode::Required => "error-required",
+            ErrorCode::InvalidDate => "error-invalid-date",
+            ErrorCode::InvalidNumber => "error-invalid-number",
+            ErrorCode::InvalidIban => "error-invalid-iban",
+            ErrorCode::InvalidIbanCheckDigits => "error-invalid-iban-check-digits",
+            ErrorCode::InvalidIdCardNumber => "error-invalid-id-card-number",
+            ErrorCode::TaxIdLength => "error-tax-id-length",
+            ErrorCode::InvalidTaxId => "error-invalid-tax-id",
+            ErrorCode::PostNumberLength => "error-post-number-length",
+            ErrorCode::InvalidPostNumber => "error-invalid-post-number",
+            ErrorCode::SocialSecurityNumberLength => "error-social-security-number-length",
+            ErrorCode::InvalidRegionCode => "error-invalid-region-code",
+            ErrorCode::InvalidDayOfBirth => "error-invalid-day-of-birth",
+            ErrorCode::InvalidMonthOfBirth => "error-invalid-month-of-birth",
+            ErrorCode::InvalidYearOfBirth => "error-invalid-year-of-birth",
+            ErrorCode::InvalidSerialNumber => "error-invalid-serial-number",
+            ErrorCode::InvalidCheckDigit => "error-invalid-check-digit",
+            ErrorCode::InvalidLeadTime => "error-invalid-lead-time",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DataError {
+    pub code: ErrorCode,
+    /// The rejected value, which is passed to the message as `$value`.
+    pub value: String,
+}
+
+impl DataError {
+    pub fn new(code: ErrorCode, value: impl Into<String>) -> DataError {
+        DataError {
+            code,
+            value: value.into(),
+        }
+    }
+}
+
+/// Only used for logs and deserialization errors, the UI shows the
+/// localized message.
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.code.message_id(), self.value)
+    }
+}
+
+impl Error for DataError {}
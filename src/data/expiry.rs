@@ -0,0 +1,163 @@
+use super::error::{DataError, ErrorCode};
+use chrono::prelude::*;
+use chrono::Duration;
+use std::str::FromStr;
+
+/// State of a dated document. The variants are ordered by urgency.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ExpiryStatus {
+    Expired,
+    ExpiringSoon,
+    Valid,
+}
+
+impl ExpiryStatus {
+    /// Documents are valid through the end of their last day, which is the
+    /// case for a `time_until_expiration` of zero. Documents expiring within
+    /// `threshold` are considered to expire soon.
+    pub fn new(time_until_expiration: Duration, threshold: Duration) -> ExpiryStatus {
+        if time_until_expiration < Duration::zero() {
+            ExpiryStatus::Expired
+        } else if time_until_expiration <= threshold {
+            ExpiryStatus::ExpiringSoon
+        } else {
+            ExpiryStatus::Valid
+        }
+    }
+}
+
+/// The expiration date of a single dated document.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Expiry {
+    /// Name of the document, e.g. `Personalausweis`. It identifies the
+    /// events of exported calendars, so it does not depend on the language.
+    pub title: String,
+    /// Id of the localized name of the document, e.g. `document-id-card`.
+    pub name: &'static str,
+    /// Value identifying the document, e.g. the card number.
+    pub reference: String,
+    /// The document is valid through the end of this day.
+    pub valid_until: NaiveDate,
+}
+
+impl Expiry {
+    pub fn time_until_expiration(&self, today: NaiveDate) -> Duration {
+        self.valid_until - today
+    }
+
+    /// Whether the document expires within `window` or has already expired.
+    pub fn is_due(&self, window: Duration, today: NaiveDate) -> bool {
+        self.status(window, today) != ExpiryStatus::Valid
+    }
+
+    pub fn status(&self, threshold: Duration, today: NaiveDate) -> ExpiryStatus {
+        ExpiryStatus::new(self.time_until_expiration(today), threshold)
+    }
+}
+
+/// The current date in the local time zone.
+pub fn today() -> NaiveDate {
+    Local::today().naive_local()
+}
+
+/// Lead times before an expiry at which the user wants to be reminded.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ReminderConfig {
+    pub lead_times: Vec<Duration>,
+}
+
+const DEFAULT_LEAD_TIMES_IN_DAYS: [i64; 3] = [90, 30, 7];
+
+impl Default for ReminderConfig {
+    fn default() -> ReminderConfig {
+        ReminderConfig {
+            lead_times: DEFAULT_LEAD_TIMES_IN_DAYS
+                .iter()
+                .map(|days| Duration::days(*days))
+                .collect(),
+        }
+    }
+}
+
+impl ReminderConfig {
+    /// The largest lead time. Documents expiring within this window are
+    /// considered due.
+    pub fn warning_window(&self) -> Duration {
+        self.lead_times
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or_else(Duration::zero)
+    }
+}
+
+impl FromStr for ReminderConfig {
+    type Err = DataError;
+
+    /// Parse a comma separated list of lead times in days, e.g. `90,30,7`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let lead_times = value
+            .split(',')
+            .map(|days| days.trim())
+            .filter(|days| !days.is_empty())
+            .map(|days| {
+                days.parse::<u32>()
+                    .map(|days| Duration::days(days.into()))
+                    .map_err(|_| DataError::new(ErrorCode::InvalidLeadTime, days))
+            })
+            .collect::<Result<Vec<Duration>, DataError>>()?;
+
+        Ok(ReminderConfig { lead_times })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_the_reminder_lead_times() {
+        let config = ReminderConfig::from_str("30, 7,1").unwrap();
+
+        assert_eq!(
+            config.lead_times,
+            vec![Duration::days(30), Duration::days(7), Duration::days(1)]
+        );
+        assert_eq!(config.warning_window(), Duration::days(30));
+    }
+
+    #[test]
+    fn should_reject_invalid_reminder_lead_times() {
+        assert!(ReminderConfig::from_str("30,soon").is_err());
+    }
+
+    #[test]
+    fn should_order_the_status_by_urgency() {
+        let threshold = Duration::days(30);
+
+        let expired = ExpiryStatus::new(Duration::days(-12), threshold);
+        let last_day = ExpiryStatus::new(Duration::zero(), threshold);
+        let expiring_soon = ExpiryStatus::new(Duration::days(12), threshold);
+        let valid = ExpiryStatus::new(Duration::days(42), threshold);
+
+        assert_eq!(expired, ExpiryStatus::Expired);
+        assert_eq!(last_day, ExpiryStatus::ExpiringSoon);
+        assert_eq!(expiring_soon, ExpiryStatus::ExpiringSoon);
+        assert_eq!(valid, ExpiryStatus::Valid);
+        assert!(expired < expiring_soon && expiring_soon < valid);
+    }
+
+    #[test]
+    fn should_only_be_due_within_the_warning_window() {
+        let today = NaiveDate::from_ymd(2030, 2, 1);
+        let expiry = Expiry {
+            title: "Personalausweis".into(),
+            name: "document-id-card",
+            reference: "123".into(),
+            valid_until: today + Duration::days(10),
+        };
+
+        assert!(expiry.is_due(Duration::days(30), today));
+        assert!(!expiry.is_due(Duration::days(7), today));
+    }
+}
@@ -0,0 +1,251 @@
+//! Append-only history of the changes to a profile, e.g. to look up when a
+//! bank account was added or what an old tax id was.
+//!
+//! The history is stored next to the profile with one change per line. A
+//! change holds the whole field before and after it, like it is merged, so
+//! that an old value can be restored.
+
+use super::document::Document;
+use super::merge::{document_key, group, iban_key, ProfileField};
+use super::profile::{BankAccount, Name, Profile};
+use super::template::{Entry, Template};
+use super::KeyValueItem;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+/// The value of a field. Lists hold all entries with the key of the field,
+/// usually one.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub enum FieldValue {
+    Name(Name),
+    Documents(Vec<Document>),
+    BankAccounts(Vec<BankAccount>),
+    KeyValueItems(Vec<KeyValueItem>),
+    Templates(Vec<Template>),
+    Entries(Vec<Entry>),
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Change {
+    pub timestamp: DateTime<Utc>,
+    pub field: ProfileField,
+    /// `None`, if the field was added.
+    pub old: Option<FieldValue>,
+    /// `None`, if the field was deleted.
+    pub new: Option<FieldValue>,
+}
+
+impl Change {
+    /// The old value as text, like it is shown for conflicts.
+    pub fn describe_old(&self) -> Option<String> {
+        profile_with(self.old.as_ref()).describe_field(&self.field)
+    }
+
+    /// The new value as text, like it is shown for conflicts.
+    pub fn describe_new(&self) -> Option<String> {
+        profile_with(self.new.as_ref()).describe_field(&self.field)
+    }
+
+    /// Whether the old or the new value is secret.
+    pub fn is_secret(&self) -> bool {
+        [&self.old, &self.new]
+            .into_iter()
+            .any(|value| profile_with(value.as_ref()).is_secret_field(&self.field))
+    }
+
+    /// The name of the changed bank account, template or entry, which is
+    /// not part of the field, or `None` for other fields.
+    pub fn item_name(&self) -> Option<String> {
+        match self.new.as_ref().or(self.old.as_ref())? {
+            FieldValue::BankAccounts(accounts) => {
+                accounts.first().map(|account| account.name.clone())
+            }
+            FieldValue::Templates(templates) => {
+                templates.first().map(|template| template.name.clone())
+            }
+            FieldValue::Entries(entries) => entries.first().map(|entry| entry.name.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// The changes from `old` to `new` with one change per field.
+pub fn changes(old: &Profile, new: &Profile, timestamp: DateTime<Utc>) -> Vec<Change> {
+    old.differences(new)
+        .into_iter()
+        .map(|field| Change {
+            timestamp,
+            old: old.field_value(&field),
+            new: new.field_value(&field),
+            field,
+        })
+        .collect()
+}
+
+/// The changes in `file`, the oldest first. Lines, which cannot be parsed,
+/// are skipped, e.g. a line which was not completely written, because the
+/// app was closed.
+pub fn load_history(file: &mut File) -> io::Result<Vec<Change>> {
+    let mut data = String::new();
+    file.read_to_string(&mut data)?;
+
+    Ok(data
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append `changes` to the end of `file`, which has to be opened for
+/// appending.
+pub fn append_history(file: &mut File, changes: &[Change]) -> io::Result<()> {
+    let mut data = String::new();
+    for change in changes {
+        data.push_str(&serde_json::to_string(change)?);
+        data.push('\n');
+    }
+    file.write_all(data.as_ref())?;
+
+    Ok(())
+}
+
+impl Profile {
+    /// The value of a field, `None` if the profile does not have it.
+    pub fn field_value(&self, field: &ProfileField) -> Option<FieldValue> {
+        fn cloned<T: Clone>(items: Vec<&T>) -> Option<Vec<T>> {
+            match items.is_empty() {
+                true => None,
+                false => Some(items.into_iter().cloned().collect()),
+            }
+        }
+
+        match field {
+            ProfileField::Name => Some(FieldValue::Name(self.name.clone())),
+            ProfileField::Document(type_id) => {
+                cloned(group(&self.documents(), type_id, document_key)).map(FieldValue::Documents)
+            }
+            ProfileField::BankAccount(iban) => {
+                cloned(group(&self.bank_accounts, iban, iban_key)).map(FieldValue::BankAccounts)
+            }
+            ProfileField::KeyValueItem(key) => {
+                cloned(group(&self.key_value_items, key, |item| item.key.clone()))
+                    .map(FieldValue::KeyValueItems)
+            }
+            ProfileField::Template(id) => {
+                cloned(group(&self.templates, id, |template| template.id))
+                    .map(FieldValue::Templates)
+            }
+            ProfileField::Entry(id) => {
+                cloned(group(&self.entries, id, |entry| entry.id)).map(FieldValue::Entries)
+            }
+        }
+    }
+
+    /// Replace a field with `value`, e.g. to restore it from the history.
+    /// `None` removes the field.
+    pub fn set_field_value(&mut self, field: &ProfileField, value: Option<&FieldValue>) {
+        self.copy_field(&profile_with(value), field);
+    }
+}
+
+/// An empty profile, which only has `value`.
+fn profile_with(value: Option<&FieldValue>) -> Profile {
+    let mut profile = Profile::new(String::new(), String::new());
+    match value {
+        Some(FieldValue::Name(name)) => profile.name = name.clone(),
+        Some(FieldValue::Documents(documents)) => profile.set_documents(documents.clone()),
+        Some(FieldValue::BankAccounts(accounts)) => profile.bank_accounts = accounts.clone(),
+        Some(FieldValue::KeyValueItems(items)) => profile.key_value_items = items.clone(),
+        Some(FieldValue::Templates(templates)) => profile.templates = templates.clone(),
+        Some(FieldValue::Entries(entries)) => profile.entries = entries.clone(),
+        None => {}
+    }
+
+    profile
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::tax_id::TaxId;
+    use super::super::ItemId;
+    use super::*;
+    use chrono::TimeZone;
+    use std::io::{Seek, SeekFrom};
+
+    fn bank_account(name: &str, iban: &str) -> BankAccount {
+        BankAccount {
+            id: ItemId::new(),
+            name: name.into(),
+            iban: iban.into(),
+            pinned: false,
+        }
+    }
+
+    #[test]
+    fn should_record_the_old_and_new_value_of_each_changed_field() {
+        let mut old = Profile::new("Test".into(), "Name".into());
+        old.tax_id = Some(TaxId::try_from(12_123_456_789).unwrap());
+
+        let mut new = old.clone();
+        new.tax_id = Some(TaxId::try_from(98_765_432_106).unwrap());
+        new.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+
+        let timestamp = Utc.ymd(2024, 3, 1).and_hms(12, 0, 0);
+        let changes = changes(&old, &new, timestamp);
+
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].field, ProfileField::Document("tax_id".into()));
+        assert_eq!(changes[0].timestamp, timestamp);
+        assert_eq!(
+            changes[0].describe_old(),
+            old.describe_field(&changes[0].field)
+        );
+        assert_eq!(
+            changes[0].describe_new(),
+            new.describe_field(&changes[0].field)
+        );
+        assert!(changes[0].is_secret());
+        assert_eq!(changes[1].old, None);
+        assert_eq!(changes[1].item_name(), Some("Giro".into()));
+    }
+
+    #[test]
+    fn should_restore_the_old_value_of_a_change() {
+        let mut old = Profile::new("Test".into(), "Name".into());
+        old.tax_id = Some(TaxId::try_from(12_123_456_789).unwrap());
+        old.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+
+        let mut new = old.clone();
+        new.tax_id = None;
+        new.bank_accounts.clear();
+        new.bank_accounts
+            .push(bank_account("Tagesgeld", "DE20 2020 2020 2020 2020 20"));
+
+        let mut profile = new.clone();
+        for change in changes(&old, &new, Utc::now()) {
+            profile.set_field_value(&change.field, change.old.as_ref());
+        }
+
+        assert_eq!(profile, old);
+    }
+
+    #[test]
+    fn should_append_the_changes_to_the_history() {
+        let old = Profile::new("Test".into(), "Name".into());
+        let mut new = old.clone();
+        new.name.last_name = "Neu".into();
+        let first = changes(&old, &new, Utc::now());
+        let second = changes(&new, &old, Utc::now());
+        let mut file = tempfile::tempfile().unwrap();
+
+        append_history(&mut file, &first).unwrap();
+        file.write_all(b"{\"timestamp\":").unwrap();
+        file.write_all(b"\n").unwrap();
+        append_history(&mut file, &second).unwrap();
+        file.seek(SeekFrom::Start(0)).unwrap();
+        let history = load_history(&mut file).unwrap();
+
+        assert_eq!(history, [first, second].concat());
+    }
+}
@@ -0,0 +1,78 @@
+//! Stable identifiers for list entries like bank accounts, which do not
+//! change when the entry is edited or moved. Two entries with the same
+//! name or key can be told apart by their id.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(transparent)]
+pub struct ItemId(Uuid);
+
+impl ItemId {
+    pub fn new() -> ItemId {
+        ItemId(Uuid::new_v4())
+    }
+}
+
+/// Entries of profiles without ids get a new id, when they are loaded.
+impl Default for ItemId {
+    fn default() -> ItemId {
+        ItemId::new()
+    }
+}
+
+/// Entries were numbered before, e.g. `42`. The numbers are kept as
+/// `00000000-0000-0000-0000-00000000002a`, so the same number always gets
+/// the same id.
+impl<'de> Deserialize<'de> for ItemId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<ItemId, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StoredId {
+            Uuid(Uuid),
+            Number(u64),
+        }
+
+        Ok(match StoredId::deserialize(deserializer)? {
+            StoredId::Uuid(uuid) => ItemId(uuid),
+            StoredId::Number(number) => ItemId(Uuid::from_u128(number.into())),
+        })
+    }
+}
+
+impl fmt::Display for ItemId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_create_unique_ids() {
+        assert_ne!(ItemId::new(), ItemId::new());
+    }
+
+    #[test]
+    fn should_serialize_as_uuid_string() {
+        let id: ItemId = serde_json::from_str(r#""67e55044-10b1-426f-9247-bb680e5fe0c8""#).unwrap();
+
+        assert_eq!(id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(
+            serde_json::to_string(&id).unwrap(),
+            r#""67e55044-10b1-426f-9247-bb680e5fe0c8""#
+        );
+    }
+
+    #[test]
+    fn should_keep_numbered_ids() {
+        let id: ItemId = serde_json::from_str("42").unwrap();
+
+        assert_eq!(id.to_string(), "00000000-0000-0000-0000-00000000002a");
+        assert_eq!(id, serde_json::from_str("42").unwrap());
+    }
+}
@@ -1,7 +1,12 @@
+use super::document::{Document, DocumentType, FieldSpec};
+use super::error::{DataError, ErrorCode};
+use super::expiry::{today, Expiry, ExpiryStatus};
+use super::profile::Profile;
 use chrono::prelude::*;
 use chrono::Duration;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::convert::TryFrom;
+use std::str::FromStr;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 #[serde(try_from = "&str", into = "String")]
@@ -10,7 +15,7 @@ pub struct IdCardNumber {
 }
 
 impl TryFrom<&str> for IdCardNumber {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         // See: https://www.bmi.bund.de/SharedDocs/downloads/DE/veroeffentlichungen/themen/moderne-verwaltung/ausweise/personalausweis-seriennummer.html
@@ -20,7 +25,7 @@ impl TryFrom<&str> for IdCardNumber {
         ];
 
         if !value.chars().all(|c| VALID_ID_NUMBER_CHARS.contains(&c)) {
-            Err(format!("Invalid IdCardNumber: {}", value))
+            Err(DataError::new(ErrorCode::InvalidIdCardNumber, value))
         } else {
             Ok(IdCardNumber {
                 value: String::from(value),
@@ -44,16 +49,113 @@ impl IdCardNumber {
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct IdCard {
     pub card_number: IdCardNumber,
-    pub valid_until: DateTime<Utc>,
+    /// The card is valid through the end of this day.
+    #[serde(deserialize_with = "deserialize_valid_until")]
+    pub valid_until: NaiveDate,
+}
+
+/// Accept calendar dates like `2030-02-01` as well as the timestamps like
+/// `2030-02-01T00:00:00Z` written by older versions. These timestamps were
+/// created from the entered date at midnight UTC, so their UTC date is the
+/// entered date.
+fn deserialize_valid_until<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+
+    match NaiveDate::from_str(&value) {
+        Ok(date) => Ok(date),
+        Err(_) => DateTime::parse_from_rfc3339(&value)
+            .map(|timestamp| timestamp.with_timezone(&Utc).naive_utc().date())
+            .map_err(serde::de::Error::custom),
+    }
 }
 
 impl IdCard {
     pub fn time_until_expiration(&self) -> Duration {
-        self.valid_until - Utc::now()
+        self.valid_until - today()
     }
 
     pub fn has_expired(&self) -> bool {
-        self.time_until_expiration() <= Duration::zero()
+        self.has_expired_at(&Local::now())
+    }
+
+    /// Whether the card has expired at `now` in the time zone of `now`.
+    pub fn has_expired_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> bool {
+        now.naive_local().date() > self.valid_until
+    }
+
+    /// Cards expiring within `threshold` are considered to expire soon.
+    pub fn status(&self, threshold: Duration) -> ExpiryStatus {
+        ExpiryStatus::new(self.time_until_expiration(), threshold)
+    }
+
+    pub fn expiry(&self) -> Expiry {
+        Expiry {
+            title: String::from("Personalausweis"),
+            name: IdCardType.name(),
+            reference: self.card_number.to_string(),
+            valid_until: self.valid_until,
+        }
+    }
+}
+
+pub struct IdCardType;
+
+impl IdCardType {
+    fn id_card(document: &Document) -> Option<IdCard> {
+        Some(IdCard {
+            card_number: IdCardNumber::try_from(document.value(0)).ok()?,
+            valid_until: NaiveDate::from_str(document.value(1)).ok()?,
+        })
+    }
+
+    fn document(&self, id_card: &IdCard) -> Document {
+        Document::new(
+            self.id(),
+            vec![
+                id_card.card_number.to_string(),
+                id_card.valid_until.to_string(),
+            ],
+        )
+    }
+}
+
+impl DocumentType for IdCardType {
+    fn id(&self) -> &'static str {
+        "id_card"
+    }
+
+    fn name(&self) -> &'static str {
+        "document-id-card"
+    }
+
+    fn fields(&self) -> Vec<FieldSpec> {
+        vec![
+            FieldSpec::text("field-number")
+                .validate(|value| IdCardNumber::try_from(value).map(|_| ())),
+            FieldSpec::date("field-valid-until"),
+        ]
+    }
+
+    fn format(&self, document: &Document) -> String {
+        String::from(document.value(0))
+    }
+
+    fn expiry(&self, document: &Document) -> Option<Expiry> {
+        IdCardType::id_card(document).map(|id_card| id_card.expiry())
+    }
+
+    fn read(&self, profile: &Profile) -> Option<Document> {
+        profile
+            .id_card
+            .as_ref()
+            .map(|id_card| self.document(id_card))
+    }
+
+    fn write(&self, profile: &mut Profile, document: Option<Document>) {
+        profile.id_card = document.as_ref().and_then(IdCardType::id_card);
     }
 }
 
@@ -75,19 +177,83 @@ mod test {
 
     #[test]
     fn should_correctly_detect_expired_id_cards() {
-        let card = create_id_card(Utc::now() - Duration::minutes(1));
+        let card = create_id_card(today() - Duration::days(1));
 
         assert_eq!(card.has_expired(), true);
     }
 
     #[test]
     fn should_correctly_detect_unexpired_id_cards() {
-        let card = create_id_card(Utc::now() + Duration::minutes(1));
+        let card = create_id_card(today() + Duration::days(1));
 
         assert_eq!(card.has_expired(), false);
     }
 
-    fn create_id_card(valid_until: DateTime<Utc>) -> IdCard {
+    #[test]
+    fn should_be_valid_through_the_end_of_the_last_day() {
+        let card = create_id_card(today());
+
+        assert!(!card.has_expired());
+        assert_eq!(card.status(Duration::days(30)), ExpiryStatus::ExpiringSoon);
+    }
+
+    #[test]
+    fn should_detect_expiration_in_the_local_time_zone() {
+        let card = create_id_card(NaiveDate::from_ymd(2030, 2, 1));
+        let berlin = FixedOffset::east(3600);
+        let new_york = FixedOffset::west(5 * 3600);
+
+        let before_midnight_in_berlin = berlin.ymd(2030, 2, 1).and_hms(23, 59, 59);
+        let after_midnight_in_berlin = berlin.ymd(2030, 2, 2).and_hms(0, 0, 0);
+        let same_instant_in_new_york = after_midnight_in_berlin.with_timezone(&new_york);
+
+        assert!(!card.has_expired_at(&before_midnight_in_berlin));
+        assert!(card.has_expired_at(&after_midnight_in_berlin));
+        assert!(!card.has_expired_at(&same_instant_in_new_york));
+        assert!(!card.has_expired_at(&Utc.ymd(2030, 2, 1).and_hms(23, 59, 59)));
+    }
+
+    #[test]
+    fn should_detect_id_cards_expiring_soon() {
+        let card = create_id_card(today() + Duration::days(10));
+
+        assert_eq!(card.status(Duration::days(30)), ExpiryStatus::ExpiringSoon);
+        assert_eq!(card.status(Duration::days(7)), ExpiryStatus::Valid);
+    }
+
+    #[test]
+    fn should_store_the_expiration_as_calendar_date() {
+        let card = create_id_card(NaiveDate::from_ymd(2030, 2, 1));
+
+        let json = serde_json::to_string(&card).unwrap();
+
+        assert_eq!(json, r#"{"card_number":"123","valid_until":"2030-02-01"}"#);
+        assert_eq!(serde_json::from_str::<IdCard>(&json).unwrap(), card);
+    }
+
+    #[test]
+    fn should_migrate_timestamps_to_calendar_dates() {
+        let json = r#"{"card_number":"123","valid_until":"2030-02-01T00:00:00Z"}"#;
+
+        let card: IdCard = serde_json::from_str(json).unwrap();
+
+        assert_eq!(card.valid_until, NaiveDate::from_ymd(2030, 2, 1));
+    }
+
+    #[test]
+    fn should_convert_id_cards_to_documents() {
+        let card = create_id_card(NaiveDate::from_ymd(2030, 2, 1));
+        let document = IdCardType.document(&card);
+
+        assert_eq!(document.values, vec!["123", "2030-02-01"]);
+        assert_eq!(IdCardType::id_card(&document), Some(card));
+        assert_eq!(
+            IdCardType.expiry(&document).unwrap().valid_until,
+            NaiveDate::from_ymd(2030, 2, 1)
+        );
+    }
+
+    fn create_id_card(valid_until: NaiveDate) -> IdCard {
         return IdCard {
             card_number: IdCardNumber::try_from("123").unwrap(),
             valid_until,
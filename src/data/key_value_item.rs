@@ -1,7 +1,198 @@
+use super::ItemId;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct KeyValueItem {
+    /// Items of older profiles get an id, when they are loaded.
+    #[serde(default)]
+    pub id: ItemId,
     pub key: String,
     pub value: String,
+
+    /// Secret values are masked in the UI.
+    #[serde(default)]
+    pub secret: bool,
+
+    /// Pinned items are shown first.
+    #[serde(default)]
+    pub pinned: bool,
+
+    /// Empty for items without a category.
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Suggested categories, which are shown in this order before any other
+/// category.
+pub const CATEGORIES: [&str; 4] = [
+    "Versicherungen",
+    "Verträge",
+    "Kundennummern",
+    "Zugangsdaten",
+];
+/// Section of the items without a category.
+pub const UNCATEGORIZED: &str = "Ohne Kategorie";
+
+impl KeyValueItem {
+    /// The category, or `UNCATEGORIZED`.
+    pub fn category(&self) -> &str {
+        match self.category.trim() {
+            "" => UNCATEGORIZED,
+            category => category,
+        }
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|item_tag| item_tag == tag)
+    }
+}
+
+/// Parse comma separated tags like `privat, #wlan`.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for tag in input.split(',') {
+        let tag = tag.trim().trim_start_matches('#').trim();
+        if !tag.is_empty() && !tags.iter().any(|existing| existing == tag) {
+            tags.push(String::from(tag));
+        }
+    }
+
+    tags
+}
+
+pub fn format_tags(tags: &[String]) -> String {
+    tags.join(", ")
+}
+
+/// All tags of the items, sorted alphabetically.
+pub fn all_tags<'a>(items: impl IntoIterator<Item = &'a KeyValueItem>) -> Vec<String> {
+    let mut tags: Vec<String> = items
+        .into_iter()
+        .flat_map(|item| item.tags.iter().cloned())
+        .collect();
+    tags.sort_by_key(|tag| tag.to_lowercase());
+    tags.dedup();
+
+    tags
+}
+
+/// Suggested categories first, then other categories alphabetically and
+/// the items without a category last.
+fn category_rank(category: &str) -> (usize, String) {
+    match CATEGORIES
+        .iter()
+        .position(|suggested| *suggested == category)
+    {
+        Some(index) => (index, String::new()),
+        None if category == UNCATEGORIZED => (CATEGORIES.len() + 1, String::new()),
+        None => (CATEGORIES.len(), category.to_lowercase()),
+    }
+}
+
+/// Group the items by their category, keeping the order of the items
+/// within a category.
+pub fn group_by_category<'a>(
+    items: impl IntoIterator<Item = &'a KeyValueItem>,
+) -> Vec<(String, Vec<KeyValueItem>)> {
+    let mut groups: Vec<(String, Vec<KeyValueItem>)> = Vec::new();
+
+    for item in items {
+        match groups
+            .iter_mut()
+            .find(|(category, _)| category == item.category())
+        {
+            Some((_, group)) => group.push(item.clone()),
+            None => groups.push((String::from(item.category()), vec![item.clone()])),
+        }
+    }
+    groups.sort_by_key(|(category, _)| category_rank(category));
+
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_not_be_secret_by_default() {
+        let item: KeyValueItem = serde_json::from_str(r#"{"key":"PIN","value":"1234"}"#).unwrap();
+
+        assert!(!item.secret);
+    }
+
+    #[test]
+    fn should_not_be_pinned_by_default() {
+        let item: KeyValueItem = serde_json::from_str(r#"{"key":"PIN","value":"1234"}"#).unwrap();
+
+        assert!(!item.pinned);
+    }
+
+    #[test]
+    fn should_not_have_a_category_or_tags_by_default() {
+        let item: KeyValueItem = serde_json::from_str(r#"{"key":"PIN","value":"1234"}"#).unwrap();
+
+        assert_eq!(item.category(), UNCATEGORIZED);
+        assert!(item.tags.is_empty());
+    }
+
+    #[test]
+    fn should_parse_tags() {
+        assert_eq!(
+            parse_tags(" privat, #wlan,, privat "),
+            vec![String::from("privat"), String::from("wlan")]
+        );
+        assert!(parse_tags("  ").is_empty());
+    }
+
+    #[test]
+    fn should_group_items_by_category() {
+        let item = |key: &str, category: &str| KeyValueItem {
+            id: ItemId::new(),
+            key: key.into(),
+            value: String::new(),
+            secret: false,
+            pinned: false,
+            category: category.into(),
+            tags: Vec::new(),
+        };
+        let items = vec![
+            item("WLAN", ""),
+            item("Fitnessstudio", "Mitgliedschaften"),
+            item("KFZ", "Versicherungen"),
+            item("Strom", "Verträge"),
+            item("Hausrat", "Versicherungen"),
+        ];
+
+        let groups: Vec<(String, Vec<String>)> = group_by_category(&items)
+            .into_iter()
+            .map(|(category, items)| (category, items.into_iter().map(|item| item.key).collect()))
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (
+                    "Versicherungen".into(),
+                    vec!["KFZ".into(), "Hausrat".into()]
+                ),
+                ("Verträge".into(), vec!["Strom".into()]),
+                ("Mitgliedschaften".into(), vec!["Fitnessstudio".into()]),
+                (UNCATEGORIZED.into(), vec!["WLAN".into()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_the_id() {
+        let item: KeyValueItem = serde_json::from_str(
+            r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","key":"PIN","value":"1234"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+    }
 }
@@ -0,0 +1,514 @@
+//! Three-way merge of profiles, which were changed on two machines since
+//! they were last synced, e.g. through a shared folder.
+//!
+//! The profiles are compared field by field: the name, each document and
+//! each entry of the lists. Bank accounts are matched by their IBAN, items
+//! by their key and templates and entries by their id. A field, which only
+//! changed on one side, is taken from that side. A field, which changed
+//! differently on both sides, is a conflict.
+
+use super::document::{document_type, Document};
+use super::profile::{BankAccount, Profile};
+use super::template::TemplateFieldKind;
+use super::{ItemId, KeyValueItem};
+use serde::{Deserialize, Serialize};
+
+/// Part of a profile, which is merged as a whole.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub enum ProfileField {
+    Name,
+    /// The document of the given type.
+    Document(String),
+    /// The bank accounts with the given IBAN without spaces, usually one.
+    BankAccount(String),
+    /// The items with the given key, usually one.
+    KeyValueItem(String),
+    Template(ItemId),
+    Entry(ItemId),
+}
+
+/// A field, which was changed differently in both profiles. The values are
+/// `None`, if the field is missing, e.g. because it was deleted.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Conflict {
+    pub field: ProfileField,
+    pub base: Option<String>,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    /// Whether one of the values is secret and should be masked.
+    pub is_secret: bool,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Merge {
+    /// The merged profile with the local value of each conflict.
+    pub profile: Profile,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Merge the changes, which were made to `base` in `local` and `remote`.
+/// The order of the lists is taken from `local`.
+pub fn merge(base: &Profile, local: &Profile, remote: &Profile) -> Merge {
+    let local_changes = base.differences(local);
+    let differences = local.differences(remote);
+    let mut profile = local.clone();
+    let mut conflicts = Vec::new();
+
+    for field in base.differences(remote) {
+        if !local_changes.contains(&field) {
+            profile.copy_field(remote, &field);
+        } else if differences.contains(&field) {
+            conflicts.push(Conflict {
+                base: base.describe_field(&field),
+                local: local.describe_field(&field),
+                remote: remote.describe_field(&field),
+                is_secret: local.is_secret_field(&field) || remote.is_secret_field(&field),
+                field,
+            });
+        }
+    }
+
+    Merge { profile, conflicts }
+}
+
+impl Profile {
+    /// The fields, in which this profile differs from `other`.
+    pub fn differences(&self, other: &Profile) -> Vec<ProfileField> {
+        let mut fields = Vec::new();
+        if self.name != other.name {
+            fields.push(ProfileField::Name);
+        }
+
+        fields.extend(
+            differing_keys(
+                &self.documents(),
+                &other.documents(),
+                document_key,
+                |document, other| document == other,
+            )
+            .into_iter()
+            .map(ProfileField::Document),
+        );
+        fields.extend(
+            differing_keys(
+                &self.bank_accounts,
+                &other.bank_accounts,
+                iban_key,
+                // Accounts, which were added on both machines, have
+                // different ids.
+                |account, other| {
+                    BankAccount {
+                        id: other.id,
+                        ..account.clone()
+                    } == *other
+                },
+            )
+            .into_iter()
+            .map(ProfileField::BankAccount),
+        );
+        fields.extend(
+            differing_keys(
+                &self.key_value_items,
+                &other.key_value_items,
+                |item| item.key.clone(),
+                |item, other| {
+                    KeyValueItem {
+                        id: other.id,
+                        ..item.clone()
+                    } == *other
+                },
+            )
+            .into_iter()
+            .map(ProfileField::KeyValueItem),
+        );
+        fields.extend(
+            differing_keys(
+                &self.templates,
+                &other.templates,
+                |template| template.id,
+                |template, other| template == other,
+            )
+            .into_iter()
+            .map(ProfileField::Template),
+        );
+        fields.extend(
+            differing_keys(
+                &self.entries,
+                &other.entries,
+                |entry| entry.id,
+                |entry, other| entry == other,
+            )
+            .into_iter()
+            .map(ProfileField::Entry),
+        );
+
+        fields
+    }
+
+    /// Replace a field with the one of `other`. New list entries are added
+    /// at their position in `other`.
+    pub fn copy_field(&mut self, other: &Profile, field: &ProfileField) {
+        match field {
+            ProfileField::Name => self.name = other.name.clone(),
+            ProfileField::Document(type_id) => {
+                let mut documents = self.documents();
+                copy_group(&mut documents, &other.documents(), type_id, document_key);
+                self.set_documents(documents);
+            }
+            ProfileField::BankAccount(iban) => copy_group(
+                &mut self.bank_accounts,
+                &other.bank_accounts,
+                iban,
+                iban_key,
+            ),
+            ProfileField::KeyValueItem(key) => copy_group(
+                &mut self.key_value_items,
+                &other.key_value_items,
+                key,
+                |item| item.key.clone(),
+            ),
+            ProfileField::Template(id) => {
+                copy_group(&mut self.templates, &other.templates, id, |template| {
+                    template.id
+                })
+            }
+            ProfileField::Entry(id) => {
+                copy_group(&mut self.entries, &other.entries, id, |entry| entry.id)
+            }
+        }
+    }
+
+    /// The value of a field as text, `None` if the profile does not have
+    /// the field.
+    pub fn describe_field(&self, field: &ProfileField) -> Option<String> {
+        let values: Vec<String> = match field {
+            ProfileField::Name => {
+                return Some(format!("{} {}", self.name.first_name, self.name.last_name))
+            }
+            ProfileField::Document(type_id) => group(&self.documents(), type_id, document_key)
+                .into_iter()
+                .map(|document| match document.document_type() {
+                    Some(document_type) => document_type.format(document),
+                    None => document.values.join(", "),
+                })
+                .collect(),
+            ProfileField::BankAccount(iban) => group(&self.bank_accounts, iban, iban_key)
+                .into_iter()
+                .map(|account| format!("{}: {}", account.name, account.iban))
+                .collect(),
+            ProfileField::KeyValueItem(key) => self
+                .key_value_items
+                .iter()
+                .filter(|item| item.key == *key)
+                .map(|item| format!("{}: {}", item.key, item.value))
+                .collect(),
+            ProfileField::Template(id) => self
+                .templates
+                .iter()
+                .filter(|template| template.id == *id)
+                .map(|template| template.name.clone())
+                .collect(),
+            ProfileField::Entry(id) => self
+                .entries
+                .iter()
+                .filter(|entry| entry.id == *id)
+                .map(|entry| {
+                    let values: Vec<String> = entry
+                        .values
+                        .iter()
+                        .map(|value| format!("{}: {}", value.label, value.value))
+                        .collect();
+                    format!("{} ({})", entry.name, values.join(", "))
+                })
+                .collect(),
+        };
+
+        match values.is_empty() {
+            true => None,
+            false => Some(values.join("; ")),
+        }
+    }
+
+    /// Whether the value of the field is secret, like IBANs and the tax id.
+    pub fn is_secret_field(&self, field: &ProfileField) -> bool {
+        match field {
+            ProfileField::Name | ProfileField::Template(_) => false,
+            ProfileField::Document(type_id) => {
+                document_type(type_id).is_some_and(|document_type| document_type.is_secret())
+            }
+            ProfileField::BankAccount(_) => true,
+            ProfileField::KeyValueItem(key) => self
+                .key_value_items
+                .iter()
+                .any(|item| item.key == *key && item.secret),
+            ProfileField::Entry(id) => self
+                .entries
+                .iter()
+                .filter(|entry| entry.id == *id)
+                .flat_map(|entry| entry.values.iter())
+                .any(|value| value.kind == TemplateFieldKind::Secret),
+        }
+    }
+}
+
+pub(super) fn document_key(document: &Document) -> String {
+    document.type_id.clone()
+}
+
+pub(super) fn iban_key(account: &BankAccount) -> String {
+    account
+        .iban
+        .split_whitespace()
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// The entries of a list with the given key.
+pub(super) fn group<'a, T, K: PartialEq>(
+    items: &'a [T],
+    key: &K,
+    key_of: impl Fn(&T) -> K,
+) -> Vec<&'a T> {
+    items.iter().filter(|item| key_of(item) == *key).collect()
+}
+
+/// The keys of the entries, which differ between both lists, in the order
+/// in which they first appear.
+fn differing_keys<T, K: PartialEq>(
+    items: &[T],
+    other: &[T],
+    key_of: impl Fn(&T) -> K,
+    is_same: impl Fn(&T, &T) -> bool,
+) -> Vec<K> {
+    let mut keys: Vec<K> = Vec::new();
+    for key in items.iter().chain(other).map(&key_of) {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    keys.into_iter()
+        .filter(|key| {
+            let entries = group(items, key, &key_of);
+            let other_entries = group(other, key, &key_of);
+
+            entries.len() != other_entries.len()
+                || entries
+                    .iter()
+                    .zip(&other_entries)
+                    .any(|(item, other)| !is_same(item, other))
+        })
+        .collect()
+}
+
+/// Replace the entries with the given key by the ones of `other`.
+fn copy_group<T: Clone, K: PartialEq>(
+    items: &mut Vec<T>,
+    other: &[T],
+    key: &K,
+    key_of: impl Fn(&T) -> K,
+) {
+    let position = items.iter().position(|item| key_of(item) == *key);
+    items.retain(|item| key_of(item) != *key);
+
+    let position = position
+        .or_else(|| other.iter().position(|item| key_of(item) == *key))
+        .map_or(items.len(), |position| position.min(items.len()));
+    for (offset, item) in group(other, key, &key_of).into_iter().enumerate() {
+        items.insert(position + offset, item.clone());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::post_number::PostNumber;
+    use super::super::tax_id::TaxId;
+    use super::super::template::{Entry, Template};
+    use super::*;
+
+    fn bank_account(name: &str, iban: &str) -> BankAccount {
+        BankAccount {
+            id: ItemId::new(),
+            name: name.into(),
+            iban: iban.into(),
+            pinned: false,
+        }
+    }
+
+    fn template(name: &str) -> Template {
+        Template {
+            id: ItemId::new(),
+            name: name.into(),
+            fields: vec![],
+        }
+    }
+
+    fn entry(template: &Template, name: &str) -> Entry {
+        Entry {
+            id: ItemId::new(),
+            template_id: template.id,
+            name: name.into(),
+            values: vec![],
+        }
+    }
+
+    fn item(key: &str, value: &str) -> KeyValueItem {
+        KeyValueItem {
+            id: ItemId::new(),
+            key: key.into(),
+            value: value.into(),
+            secret: false,
+            pinned: false,
+            category: String::new(),
+            tags: vec![],
+        }
+    }
+
+    #[test]
+    fn should_take_the_differing_fields_of_another_profile() {
+        let mut profile = Profile::new("Test".into(), "Name".into());
+        profile.tax_id = Some(TaxId::try_from(12_123_456_789).unwrap());
+        profile.other_documents = vec![Document::new("library_card", vec!["42".into()])];
+
+        let mut other = Profile::new("Other".into(), "Name".into());
+        other.tax_id = profile.tax_id;
+        other.post_number = Some(PostNumber::try_from(123_456_789).unwrap());
+        other.bank_accounts = vec![bank_account("Konto", "DE10 1010 1010 1010 1010 10")];
+
+        let differences = profile.differences(&other);
+        assert_eq!(
+            differences,
+            vec![
+                ProfileField::Name,
+                ProfileField::Document("library_card".into()),
+                ProfileField::Document("post_number".into()),
+                ProfileField::BankAccount("DE10101010101010101010".into()),
+            ]
+        );
+
+        profile.copy_field(&other, &ProfileField::Document("post_number".into()));
+        profile.copy_field(&other, &ProfileField::Document("library_card".into()));
+        assert_eq!(profile.post_number, other.post_number);
+        assert!(profile.other_documents.is_empty());
+        assert_eq!(profile.name.first_name, "Test");
+
+        for field in differences {
+            profile.copy_field(&other, &field);
+        }
+        assert_eq!(profile, other);
+    }
+
+    #[test]
+    fn should_merge_the_changes_of_both_sides() {
+        let mut base = Profile::new("Test".into(), "Name".into());
+        base.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+        base.key_value_items = vec![item("PIN", "1234"), item("Kundennummer", "42")];
+
+        let mut local = base.clone();
+        local.name.last_name = "Neu".into();
+        local
+            .bank_accounts
+            .push(bank_account("Tagesgeld", "DE20 2020 2020 2020 2020 20"));
+        local.key_value_items.remove(1);
+
+        let mut remote = base.clone();
+        remote.key_value_items[0].value = "4321".into();
+        remote.key_value_items.insert(0, item("Mitglied", "7"));
+        remote.tax_id = Some(TaxId::try_from(12_123_456_789).unwrap());
+
+        let merge = merge(&base, &local, &remote);
+
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.profile.name.last_name, "Neu");
+        assert_eq!(merge.profile.tax_id, remote.tax_id);
+        assert_eq!(merge.profile.bank_accounts, local.bank_accounts);
+        assert_eq!(
+            merge.profile.key_value_items,
+            vec![
+                remote.key_value_items[0].clone(),
+                remote.key_value_items[1].clone()
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_conflicting_changes() {
+        let mut base = Profile::new("Test".into(), "Name".into());
+        base.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+        base.key_value_items = vec![item("PIN", "1234")];
+
+        let mut local = base.clone();
+        local.bank_accounts.clear();
+        local.key_value_items[0].value = "1111".into();
+        local.post_number = Some(PostNumber::try_from(123_456_789).unwrap());
+
+        let mut remote = base.clone();
+        remote.bank_accounts[0].name = "Girokonto".into();
+        remote.key_value_items[0].value = "2222".into();
+        remote.post_number = local.post_number;
+
+        let merge = merge(&base, &local, &remote);
+
+        assert_eq!(merge.profile, local);
+        assert_eq!(
+            merge.conflicts,
+            vec![
+                Conflict {
+                    field: ProfileField::BankAccount("DE10101010101010101010".into()),
+                    base: Some("Giro: DE10 1010 1010 1010 1010 10".into()),
+                    local: None,
+                    remote: Some("Girokonto: DE10 1010 1010 1010 1010 10".into()),
+                    is_secret: true,
+                },
+                Conflict {
+                    field: ProfileField::KeyValueItem("PIN".into()),
+                    base: Some("PIN: 1234".into()),
+                    local: Some("PIN: 1111".into()),
+                    remote: Some("PIN: 2222".into()),
+                    is_secret: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_match_entries_added_on_both_sides_without_their_id() {
+        let base = Profile::new("Test".into(), "Name".into());
+
+        let mut local = base.clone();
+        local.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+
+        let mut remote = base.clone();
+        remote.bank_accounts = vec![bank_account("Giro", "DE10 1010 1010 1010 1010 10")];
+
+        let merge = merge(&base, &local, &remote);
+
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(merge.profile, local);
+    }
+
+    #[test]
+    fn should_keep_templates_and_entries_added_on_both_sides() {
+        let base = Profile::new("Test".into(), "Name".into());
+
+        let mut local = base.clone();
+        local.templates = vec![template("Versicherung")];
+        local.entries = vec![entry(&local.templates[0], "KFZ")];
+
+        let mut remote = base.clone();
+        remote.templates = vec![template("Vertrag")];
+        remote.entries = vec![entry(&remote.templates[0], "Handy")];
+
+        let merge = merge(&base, &local, &remote);
+
+        assert!(merge.conflicts.is_empty());
+        assert_eq!(
+            merge.profile.templates,
+            vec![remote.templates[0].clone(), local.templates[0].clone()]
+        );
+        assert_eq!(
+            merge.profile.entries,
+            vec![remote.entries[0].clone(), local.entries[0].clone()]
+        );
+    }
+}
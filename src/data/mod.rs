@@ -1,13 +1,37 @@
+mod bundle;
+mod calendar;
+mod document;
+mod error;
+mod expiry;
+mod history;
+mod id;
 mod id_card;
 mod key_value_item;
+mod merge;
+mod order;
 mod post_number;
 mod profile;
+mod search;
+mod settings;
 mod social_security_number;
 mod tax_id;
+mod template;
 
+pub use bundle::*;
+pub use calendar::*;
+pub use document::*;
+pub use error::*;
+pub use expiry::*;
+pub use history::*;
+pub use id::*;
 pub use id_card::*;
 pub use key_value_item::*;
+pub use merge::*;
+pub use order::*;
 pub use post_number::*;
 pub use profile::*;
+pub use search::*;
+pub use settings::*;
 pub use social_security_number::*;
 pub use tax_id::*;
+pub use template::*;
@@ -0,0 +1,182 @@
+//! Order of bank accounts and miscellaneous items, which the user can
+//! change. Pinned items always come first, items are only moved within the
+//! pinned or the other items of their section.
+
+use super::{BankAccount, ItemId, KeyValueItem};
+
+pub trait Orderable {
+    fn id(&self) -> ItemId;
+    fn is_pinned(&self) -> bool;
+    fn set_pinned(&mut self, pinned: bool);
+
+    /// Items are shown in sections, e.g. the categories of miscellaneous
+    /// items.
+    fn section(&self) -> &str {
+        ""
+    }
+}
+
+impl Orderable for BankAccount {
+    fn id(&self) -> ItemId {
+        self.id
+    }
+
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+    }
+}
+
+impl Orderable for KeyValueItem {
+    fn id(&self) -> ItemId {
+        self.id
+    }
+
+    fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+    }
+
+    fn section(&self) -> &str {
+        self.category()
+    }
+}
+
+/// Move the pinned items to the front, keeping their order.
+pub fn pinned_first<T: Orderable>(items: &mut [T]) {
+    items.sort_by_key(|item| !item.is_pinned());
+}
+
+/// Move the item by `steps` within the items of its section, negative
+/// steps move it up. Returns whether the item was moved.
+pub fn move_item<T: Orderable>(items: &mut Vec<T>, id: ItemId, steps: isize) -> bool {
+    let index = match items.iter().position(|item| item.id() == id) {
+        Some(index) => index,
+        None => return false,
+    };
+
+    // Indices of the items, between which the item can be moved.
+    let neighbours: Vec<usize> = (0..items.len())
+        .filter(|other| {
+            items[*other].is_pinned() == items[index].is_pinned()
+                && items[*other].section() == items[index].section()
+        })
+        .collect();
+    let position = neighbours.iter().position(|other| *other == index).unwrap();
+    let target = (position as isize + steps).clamp(0, neighbours.len() as isize - 1) as usize;
+    if target == position {
+        return false;
+    }
+
+    let item = items.remove(index);
+    items.insert(neighbours[target], item);
+
+    true
+}
+
+/// Pin the item behind the other pinned items or unpin it and move it in
+/// front of the other items. Returns whether the item exists.
+pub fn toggle_pin<T: Orderable>(items: &mut Vec<T>, id: ItemId) -> bool {
+    let index = match items.iter().position(|item| item.id() == id) {
+        Some(index) => index,
+        None => return false,
+    };
+
+    let mut item = items.remove(index);
+    item.set_pinned(!item.is_pinned());
+
+    let pinned = items.iter().filter(|item| item.is_pinned()).count();
+    items.insert(pinned, item);
+
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn create_items(pinned: &[bool]) -> Vec<KeyValueItem> {
+        pinned
+            .iter()
+            .enumerate()
+            .map(|(index, pinned)| KeyValueItem {
+                id: ItemId::new(),
+                key: index.to_string(),
+                value: String::new(),
+                secret: false,
+                pinned: *pinned,
+                category: String::new(),
+                tags: Vec::new(),
+            })
+            .collect()
+    }
+
+    fn keys(items: &[KeyValueItem]) -> Vec<&str> {
+        items.iter().map(|item| item.key.as_str()).collect()
+    }
+
+    #[test]
+    fn should_move_items_up_and_down() {
+        let mut items = create_items(&[false, false, false]);
+        let id = items[0].id;
+
+        assert!(move_item(&mut items, id, 2));
+        assert_eq!(keys(&items), vec!["1", "2", "0"]);
+        assert!(move_item(&mut items, id, -1));
+        assert_eq!(keys(&items), vec!["1", "0", "2"]);
+    }
+
+    #[test]
+    fn should_keep_items_within_the_pinned_items() {
+        let mut items = create_items(&[true, true, false]);
+        let pinned_id = items[1].id;
+        let other_id = items[2].id;
+
+        assert!(!move_item(&mut items, pinned_id, 5));
+        assert!(!move_item(&mut items, other_id, -5));
+        assert_eq!(keys(&items), vec!["0", "1", "2"]);
+    }
+
+    #[test]
+    fn should_move_items_within_their_section() {
+        let mut items = create_items(&[false, false, false, false]);
+        items[1].category = "Verträge".into();
+        items[3].category = "Verträge".into();
+        let id = items[1].id;
+
+        assert!(move_item(&mut items, id, 1));
+        assert_eq!(keys(&items), vec!["0", "2", "3", "1"]);
+        assert!(!move_item(&mut items, id, 1));
+        assert!(move_item(&mut items, id, -1));
+        assert_eq!(keys(&items), vec!["0", "2", "1", "3"]);
+    }
+
+    #[test]
+    fn should_move_pinned_items_behind_the_other_pinned_items() {
+        let mut items = create_items(&[true, false, false]);
+        let id = items[2].id;
+
+        assert!(toggle_pin(&mut items, id));
+        assert_eq!(keys(&items), vec!["0", "2", "1"]);
+        assert!(items[1].pinned);
+
+        assert!(toggle_pin(&mut items, id));
+        assert_eq!(keys(&items), vec!["0", "2", "1"]);
+        assert!(!items[1].pinned);
+    }
+
+    #[test]
+    fn should_sort_pinned_items_first() {
+        let mut items = create_items(&[false, true, false, true]);
+
+        pinned_first(&mut items);
+
+        assert_eq!(keys(&items), vec!["1", "3", "0", "2"]);
+    }
+}
@@ -1,3 +1,6 @@
+use super::document::{Document, DocumentType, FieldSpec};
+use super::error::{DataError, ErrorCode};
+use super::profile::Profile;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
@@ -11,35 +14,30 @@ const MIN_POST_NUMBER: u32 = 100_000_000;
 const MAX_POST_NUMBER: u32 = 999_999_999;
 
 impl TryFrom<u32> for PostNumber {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(value: u32) -> Result<Self, Self::Error> {
         // todo: find correct constraints and enforce in here
-        if value < MIN_POST_NUMBER {
-            return Err(format!(
-                "PostNumber to small: must have exactly 9 digits {}",
-                value
+        if !(MIN_POST_NUMBER..=MAX_POST_NUMBER).contains(&value) {
+            return Err(DataError::new(
+                ErrorCode::PostNumberLength,
+                value.to_string(),
             ));
-        } else if value > MAX_POST_NUMBER {
-            return Err(format!(
-                "PostNumber to large: must have exactly 9 digits {}",
-                value
-            ));
-        } else {
-            Ok(PostNumber { value })
         }
+
+        Ok(PostNumber { value })
     }
 }
 
 impl TryFrom<&str> for PostNumber {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let clean: String = value.split_whitespace().collect();
 
         match clean.parse::<u32>() {
             Ok(number) => PostNumber::try_from(number),
-            Err(_) => Err(format!("PostNumber invalid: {}", value)),
+            Err(_) => Err(DataError::new(ErrorCode::InvalidPostNumber, value)),
         }
     }
 }
@@ -68,6 +66,40 @@ impl PostNumber {
     }
 }
 
+pub struct PostNumberType;
+
+impl DocumentType for PostNumberType {
+    fn id(&self) -> &'static str {
+        "post_number"
+    }
+
+    fn name(&self) -> &'static str {
+        "document-post-number"
+    }
+
+    fn fields(&self) -> Vec<FieldSpec> {
+        vec![FieldSpec::text("field-post-number")
+            .validate(|value| PostNumber::try_from(value).map(|_| ()))]
+    }
+
+    fn parse(&self, values: &[String]) -> Result<Document, DataError> {
+        let post_number = PostNumber::try_from(values.first().map(String::as_str).unwrap_or(""))?;
+
+        Ok(Document::new(self.id(), vec![post_number.to_string()]))
+    }
+
+    fn read(&self, profile: &Profile) -> Option<Document> {
+        profile
+            .post_number
+            .map(|post_number| Document::new(self.id(), vec![post_number.to_string()]))
+    }
+
+    fn write(&self, profile: &mut Profile, document: Option<Document>) {
+        profile.post_number =
+            document.and_then(|document| PostNumber::try_from(document.value(0)).ok());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
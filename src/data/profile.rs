@@ -1,8 +1,11 @@
+use super::document::{document_types, Document};
+use super::expiry::Expiry;
 use super::id_card::IdCard;
 use super::post_number::PostNumber;
 use super::social_security_number::SocialSecurityNumber;
 use super::tax_id::TaxId;
-use super::KeyValueItem;
+use super::template::{Entry, Template};
+use super::{ItemId, KeyValueItem};
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{self, Read, Write};
@@ -16,8 +19,15 @@ pub struct Name {
 
 #[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
 pub struct BankAccount {
+    /// Accounts of older profiles get an id, when they are loaded.
+    #[serde(default)]
+    pub id: ItemId,
     pub name: String,
     pub iban: String,
+
+    /// Pinned accounts are shown first.
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
@@ -31,6 +41,16 @@ pub struct Profile {
 
     #[serde(default)]
     pub key_value_items: Vec<KeyValueItem>,
+
+    #[serde(default)]
+    pub templates: Vec<Template>,
+
+    #[serde(default)]
+    pub entries: Vec<Entry>,
+
+    /// Documents of types without a field of their own.
+    #[serde(default, rename = "documents", skip_serializing_if = "Vec::is_empty")]
+    pub other_documents: Vec<Document>,
 }
 
 impl Profile {
@@ -46,8 +66,59 @@ impl Profile {
             tax_id: None,
             bank_accounts: vec![],
             key_value_items: vec![],
+            templates: vec![],
+            entries: vec![],
+            other_documents: vec![],
+        }
+    }
+
+    /// All documents in the order of their registered types, followed by
+    /// documents of unknown types.
+    pub fn documents(&self) -> Vec<Document> {
+        let mut documents: Vec<Document> = document_types()
+            .iter()
+            .filter_map(|document_type| document_type.read(self))
+            .collect();
+        documents.extend(
+            self.other_documents
+                .iter()
+                .filter(|document| document.document_type().is_none())
+                .cloned(),
+        );
+
+        documents
+    }
+
+    /// Replace all documents of this profile.
+    pub fn set_documents(&mut self, documents: Vec<Document>) {
+        self.other_documents = documents
+            .iter()
+            .filter(|document| document.document_type().is_none())
+            .cloned()
+            .collect();
+
+        for document_type in document_types() {
+            let document = documents
+                .iter()
+                .find(|document| document.type_id == document_type.id())
+                .cloned();
+            document_type.write(self, document);
         }
     }
+
+    /// All expiration dates of the documents in this profile, the most
+    /// urgent first.
+    pub fn expiries(&self) -> Vec<Expiry> {
+        let mut expiries: Vec<Expiry> = self
+            .documents()
+            .iter()
+            .filter_map(Document::expiry)
+            .collect();
+        expiries.sort_by_key(|expiry| expiry.valid_until);
+
+        expiries
+    }
+
     pub fn load_from_file(file: &mut File) -> io::Result<Profile> {
         let mut data = String::new();
         file.read_to_string(&mut data)?;
@@ -55,6 +126,21 @@ impl Profile {
         data.parse()
     }
 
+    /// Load the profile and whether it was migrated from an older version,
+    /// e.g. bank accounts without an id got a new one. A migrated profile
+    /// has to be saved, otherwise it is migrated again on the next load and
+    /// gets other ids.
+    pub fn load_migrated_from_file(file: &mut File) -> io::Result<(Profile, bool)> {
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+
+        let profile: Profile = data.parse()?;
+        let stored: serde_json::Value = serde_json::from_str(&data)?;
+        let is_migrated = serde_json::to_value(&profile)? != stored;
+
+        Ok((profile, is_migrated))
+    }
+
     pub fn save_to_file(&self, file: &mut File) -> io::Result<()> {
         let data = serde_json::to_string(self)?;
         file.write_all(data.as_ref())?;
@@ -75,12 +161,14 @@ impl FromStr for Profile {
 #[cfg(test)]
 mod test {
     use super::super::id_card::IdCardNumber;
+    use super::super::template::{EntryValue, TemplateField, TemplateFieldKind};
     use super::*;
     use chrono::prelude::*;
     use std::io::Seek;
 
     #[test]
     fn should_correctly_save_the_profile_to_disk() {
+        let template_id = ItemId::new();
         let profile = Profile {
             name: Name {
                 first_name: "Test".into(),
@@ -88,19 +176,47 @@ mod test {
             },
             id_card: Some(IdCard {
                 card_number: IdCardNumber::try_from("48328FGW9").unwrap(),
-                valid_until: Utc::now(),
+                valid_until: NaiveDate::from_ymd(2031, 3, 1),
             }),
             social_security_number: Some(SocialSecurityNumber::try_from("50 010101 N012").unwrap()),
             tax_id: Some(TaxId::try_from(12_123_456_789).unwrap()),
             post_number: Some(PostNumber::try_from(123_456_789).unwrap()),
             bank_accounts: vec![BankAccount {
+                id: ItemId::new(),
                 name: "Some Account Name".into(),
                 iban: "DE10 1010 1010 1010 1010 10".into(),
+                pinned: true,
             }],
             key_value_items: vec![KeyValueItem {
+                id: ItemId::new(),
                 key: "Versicherung".into(),
                 value: "1234".into(),
+                secret: true,
+                pinned: false,
+                category: "Versicherungen".into(),
+                tags: vec!["auto".into()],
             }],
+            templates: vec![Template {
+                id: template_id,
+                name: "Versicherung".into(),
+                fields: vec![TemplateField {
+                    id: 1,
+                    label: "Policennummer".into(),
+                    kind: TemplateFieldKind::Text,
+                }],
+            }],
+            entries: vec![Entry {
+                id: ItemId::new(),
+                template_id,
+                name: "KFZ".into(),
+                values: vec![EntryValue {
+                    field_id: 1,
+                    label: "Policennummer".into(),
+                    kind: TemplateFieldKind::Text,
+                    value: "123".into(),
+                }],
+            }],
+            other_documents: vec![Document::new("library_card", vec!["42".into()])],
         };
 
         let mut file = tempfile::tempfile().unwrap();
@@ -108,7 +224,56 @@ mod test {
         profile.save_to_file(&mut file).unwrap();
         file.rewind().unwrap();
         let loaded_profile = Profile::load_from_file(&mut file).unwrap();
+        file.rewind().unwrap();
+        let (_, is_migrated) = Profile::load_migrated_from_file(&mut file).unwrap();
 
         assert_eq!(profile, loaded_profile);
+        assert!(!is_migrated);
+    }
+
+    #[test]
+    fn should_add_ids_to_bank_accounts_and_items_of_old_profiles() {
+        let profile: Profile = r#"{
+            "name": {"first_name": "Test", "last_name": "Name"},
+            "id_card": null,
+            "social_security_number": null,
+            "post_number": null,
+            "tax_id": null,
+            "bank_accounts": [
+                {"name": "Konto", "iban": "DE10 1010 1010 1010 1010 10"},
+                {"name": "Konto", "iban": "DE10 1010 1010 1010 1010 10"}
+            ],
+            "key_value_items": [{"key": "PIN", "value": "1234"}]
+        }"#
+        .parse()
+        .unwrap();
+
+        assert_ne!(profile.bank_accounts[0].id, profile.bank_accounts[1].id);
+        assert_ne!(profile.bank_accounts[0].id, profile.key_value_items[0].id);
+    }
+
+    #[test]
+    fn should_keep_the_ids_once_the_migrated_profile_is_saved() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(
+            br#"{
+                "name": {"first_name": "Test", "last_name": "Name"},
+                "bank_accounts": [{"name": "Konto", "iban": "DE10 1010 1010 1010 1010 10"}],
+                "key_value_items": []
+            }"#,
+        )
+        .unwrap();
+        file.rewind().unwrap();
+
+        let (profile, is_migrated) = Profile::load_migrated_from_file(&mut file).unwrap();
+        assert!(is_migrated);
+
+        let mut file = tempfile::tempfile().unwrap();
+        profile.save_to_file(&mut file).unwrap();
+        file.rewind().unwrap();
+        let (loaded_profile, is_migrated) = Profile::load_migrated_from_file(&mut file).unwrap();
+
+        assert!(!is_migrated);
+        assert_eq!(loaded_profile, profile);
     }
 }
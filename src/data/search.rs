@@ -0,0 +1,124 @@
+//! Fuzzy search over the labels and values of a profile, e.g. `sprk iban`
+//! finds the IBAN of the account `Sparkasse`.
+
+/// A value, which can be found and copied in the search.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SearchItem {
+    pub label: String,
+    pub value: String,
+    pub is_secret: bool,
+}
+
+impl SearchItem {
+    pub fn new(label: impl Into<String>, value: impl Into<String>, is_secret: bool) -> SearchItem {
+        SearchItem {
+            label: label.into(),
+            value: value.into(),
+            is_secret,
+        }
+    }
+
+    /// Every term of the query has to match either the label or the value.
+    fn score(&self, query: &str) -> Option<u32> {
+        query.split_whitespace().try_fold(0, |score, term| {
+            let term_score = match (
+                fuzzy_score(term, &self.label),
+                fuzzy_score(term, &self.value),
+            ) {
+                (Some(label), Some(value)) => label.max(value),
+                (label, value) => label.or(value)?,
+            };
+
+            Some(score + term_score)
+        })
+    }
+}
+
+/// Score of `text` containing the characters of `query` in the same order,
+/// ignoring the case. Consecutive characters and characters at the start of
+/// a word score higher. Returns `None`, if `text` does not match.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let mut query = query.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut is_consecutive = false;
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        match query.peek() {
+            Some(expected) if *expected == c => {
+                query.next();
+                score += 1;
+                if is_consecutive {
+                    score += 4;
+                }
+                if !matches!(previous, Some(previous) if previous.is_alphanumeric()) {
+                    score += 2;
+                }
+                is_consecutive = true;
+            }
+            Some(_) => is_consecutive = false,
+            None => break,
+        }
+        previous = Some(c);
+    }
+
+    match query.peek() {
+        Some(_) => None,
+        None => Some(score),
+    }
+}
+
+/// The items matching `query`, the best match first. An empty query
+/// matches all items in their original order.
+pub fn search(items: &[SearchItem], query: &str) -> Vec<SearchItem> {
+    let mut results: Vec<(u32, &SearchItem)> = items
+        .iter()
+        .filter_map(|item| Some((item.score(query)?, item)))
+        .collect();
+    results.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    results.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_match_characters_in_order() {
+        assert!(fuzzy_score("spk", "Sparkasse").is_some());
+        assert!(fuzzy_score("SPK", "sparkasse").is_some());
+        assert!(fuzzy_score("kps", "Sparkasse").is_none());
+        assert_eq!(fuzzy_score("", "Sparkasse"), Some(0));
+    }
+
+    #[test]
+    fn should_prefer_consecutive_characters_and_word_starts() {
+        assert!(fuzzy_score("steuer", "Steuer-ID") > fuzzy_score("steuer", "Sonstige Teuerung"));
+        assert!(fuzzy_score("id", "Steuer-ID") > fuzzy_score("id", "Invalide"));
+    }
+
+    #[test]
+    fn should_match_every_term_in_the_label_or_value() {
+        let items = vec![
+            SearchItem::new("Sparkasse", "DE89 3704 0044 0532 0130 00", true),
+            SearchItem::new("Volksbank", "DE02 1203 0000 0000 2020 51", true),
+            SearchItem::new("Kundennummer", "12345", false),
+        ];
+
+        assert_eq!(search(&items, "spk de89"), vec![items[0].clone()]);
+        assert_eq!(search(&items, "spk volks"), vec![]);
+        assert_eq!(search(&items, "12345"), vec![items[2].clone()]);
+        assert_eq!(search(&items, " "), items);
+    }
+
+    #[test]
+    fn should_sort_the_best_match_first() {
+        let items = vec![
+            SearchItem::new("Krankenkasse", "TK", false),
+            SearchItem::new("Kasse", "1", false),
+        ];
+
+        assert_eq!(search(&items, "kasse")[0], items[1]);
+    }
+}
@@ -0,0 +1,267 @@
+//! Preferences of the user, which are stored in `settings.json` next to the
+//! profile. Missing values get their defaults, so the file may only contain
+//! the changed settings.
+
+use super::expiry::ReminderConfig;
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Read, Write};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum DateFormat {
+    /// `01.02.2030`
+    German,
+    /// `2030-02-01`
+    Iso,
+}
+
+impl DateFormat {
+    pub fn format(self, date: NaiveDate) -> String {
+        let pattern = match self {
+            DateFormat::German => "%d.%m.%Y",
+            DateFormat::Iso => "%Y-%m-%d",
+        };
+
+        date.format(pattern).to_string()
+    }
+
+    /// Format only the month and year, e.g. `02.2030`.
+    pub fn format_month(self, date: NaiveDate) -> String {
+        let pattern = match self {
+            DateFormat::German => "%m.%Y",
+            DateFormat::Iso => "%Y-%m",
+        };
+
+        date.format(pattern).to_string()
+    }
+
+    /// Id of the format in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            DateFormat::German => "german",
+            DateFormat::Iso => "iso",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<DateFormat> {
+        match id {
+            "german" => Some(DateFormat::German),
+            "iso" => Some(DateFormat::Iso),
+            _ => None,
+        }
+    }
+}
+
+/// Color theme of the UI.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Dark or light, like the operating system.
+    System,
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl Theme {
+    /// Id of the theme in the settings file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Theme::System => "system",
+            Theme::Dark => "dark",
+            Theme::Light => "light",
+            Theme::HighContrast => "high-contrast",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Theme> {
+        match id {
+            "system" => Some(Theme::System),
+            "dark" => Some(Theme::Dark),
+            "light" => Some(Theme::Light),
+            "high-contrast" => Some(Theme::HighContrast),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// Directory of the profile, the directory of the settings if empty.
+    pub data_dir: String,
+    /// Language tag of the UI, e.g. `de`, the language of the system if
+    /// empty.
+    pub language: String,
+    pub window_width: f64,
+    pub window_height: f64,
+    /// Position of the window on the screen. The window is placed by the
+    /// system, if it is missing.
+    pub window_x: Option<f64>,
+    pub window_y: Option<f64>,
+    pub date_format: DateFormat,
+    pub theme: Theme,
+    /// Reminder lead times in days. Documents expiring within the largest
+    /// one are shown in the warning banner.
+    pub reminder_days: Vec<u32>,
+    /// Seconds after which copied values are cleared from the clipboard,
+    /// `0` keeps them.
+    pub clipboard_timeout: u64,
+}
+
+pub const MIN_WINDOW_WIDTH: f64 = 600.0;
+pub const MIN_WINDOW_HEIGHT: f64 = 400.0;
+
+impl Default for Settings {
+    fn default() -> Settings {
+        let mut settings = Settings {
+            data_dir: String::new(),
+            language: String::new(),
+            window_width: 800.0,
+            window_height: 600.0,
+            window_x: None,
+            window_y: None,
+            date_format: DateFormat::German,
+            theme: Theme::System,
+            reminder_days: Vec::new(),
+            clipboard_timeout: 30,
+        };
+        settings.set_reminders(&ReminderConfig::default());
+
+        settings
+    }
+}
+
+impl Settings {
+    pub fn load_from_file(file: &mut File) -> io::Result<Settings> {
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    pub fn save_to_file(&self, file: &mut File) -> io::Result<()> {
+        let data = serde_json::to_string_pretty(self)?;
+        file.write_all(data.as_ref())?;
+
+        Ok(())
+    }
+
+    pub fn reminders(&self) -> ReminderConfig {
+        ReminderConfig {
+            lead_times: self
+                .reminder_days
+                .iter()
+                .map(|days| Duration::days((*days).into()))
+                .collect(),
+        }
+    }
+
+    pub fn set_reminders(&mut self, reminders: &ReminderConfig) {
+        self.reminder_days = reminders
+            .lead_times
+            .iter()
+            .map(|lead_time| lead_time.num_days() as u32)
+            .collect();
+    }
+
+    /// The window size, which is at least the minimal size.
+    pub fn window_size(&self) -> (f64, f64) {
+        (
+            self.window_width.max(MIN_WINDOW_WIDTH),
+            self.window_height.max(MIN_WINDOW_HEIGHT),
+        )
+    }
+
+    pub fn window_position(&self) -> Option<(f64, f64)> {
+        Some((self.window_x?, self.window_y?))
+    }
+
+    /// Remember the size and position of the window for the next start.
+    pub fn set_window(&mut self, size: (f64, f64), position: (f64, f64)) {
+        self.window_width = size.0;
+        self.window_height = size.1;
+        self.window_x = Some(position.0);
+        self.window_y = Some(position.1);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Seek;
+
+    #[test]
+    fn should_use_the_defaults_for_missing_settings() {
+        let settings: Settings = serde_json::from_str(r#"{"date_format":"iso"}"#).unwrap();
+
+        assert_eq!(settings.date_format, DateFormat::Iso);
+        assert_eq!(settings.theme, Theme::System);
+        assert_eq!(settings.reminder_days, vec![90, 30, 7]);
+        assert_eq!(settings.clipboard_timeout, 30);
+        assert_eq!(settings.window_size(), (800.0, 600.0));
+        assert_eq!(settings.window_position(), None);
+        assert!(settings.data_dir.is_empty());
+    }
+
+    #[test]
+    fn should_correctly_save_the_settings_to_disk() {
+        let mut settings = Settings::default();
+        settings.language = String::from("en");
+        settings.theme = Theme::HighContrast;
+        settings.reminder_days = vec![14];
+        settings.clipboard_timeout = 0;
+        let mut file = tempfile::tempfile().unwrap();
+
+        settings.save_to_file(&mut file).unwrap();
+        file.rewind().unwrap();
+        let loaded_settings = Settings::load_from_file(&mut file).unwrap();
+
+        assert_eq!(settings, loaded_settings);
+    }
+
+    #[test]
+    fn should_remember_the_window() {
+        let mut settings = Settings::default();
+        settings.set_window((1024.0, 300.0), (40.0, 20.0));
+
+        assert_eq!(settings.window_size(), (1024.0, MIN_WINDOW_HEIGHT));
+        assert_eq!(settings.window_position(), Some((40.0, 20.0)));
+    }
+
+    #[test]
+    fn should_parse_the_theme_ids() {
+        for theme in [
+            Theme::System,
+            Theme::Dark,
+            Theme::Light,
+            Theme::HighContrast,
+        ] {
+            let json = serde_json::to_string(&theme).unwrap();
+
+            assert_eq!(json, format!("\"{}\"", theme.id()));
+            assert_eq!(Theme::from_id(theme.id()), Some(theme));
+        }
+    }
+
+    #[test]
+    fn should_format_dates() {
+        let date = NaiveDate::from_ymd(2030, 2, 1);
+
+        assert_eq!(DateFormat::German.format(date), "01.02.2030");
+        assert_eq!(DateFormat::Iso.format(date), "2030-02-01");
+        assert_eq!(DateFormat::German.format_month(date), "02.2030");
+        assert_eq!(DateFormat::Iso.format_month(date), "2030-02");
+    }
+
+    #[test]
+    fn should_convert_the_reminder_days() {
+        let mut settings = Settings::default();
+        settings.set_reminders(&"60, 14".parse().unwrap());
+
+        assert_eq!(settings.reminder_days, vec![60, 14]);
+        assert_eq!(settings.reminders().warning_window(), Duration::days(60));
+    }
+}
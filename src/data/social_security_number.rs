@@ -1,3 +1,6 @@
+use super::document::{Document, DocumentType, FieldSpec};
+use super::error::{DataError, ErrorCode};
+use super::profile::Profile;
 use serde::{Deserialize, Serialize};
 use std::char;
 use std::convert::TryFrom;
@@ -62,7 +65,7 @@ enum RegionCode {
 }
 
 impl TryFrom<&str> for RegionCode {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(v: &str) -> Result<Self, Self::Error> {
         match v {
@@ -121,7 +124,7 @@ impl TryFrom<&str> for RegionCode {
             "81" => Ok(Self::KnappschaftBahnSeeHR),
             "82" => Ok(Self::KnappschaftBahnSeeBWBRPS),
             "89" => Ok(Self::KnappschaftBahnSeeBMVSAST),
-            _ => Err(format!("Invalid Region Code: {}", v)),
+            _ => Err(DataError::new(ErrorCode::InvalidRegionCode, v)),
         }
     }
 }
@@ -139,38 +142,38 @@ pub struct SocialSecurityNumber {
 }
 
 impl TryFrom<&str> for SocialSecurityNumber {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(v: &str) -> Result<Self, Self::Error> {
         let clean: String = v.split_whitespace().collect();
         if clean.len() != 12 {
-            return Err(format!("Social security number has wrong length: {}", v));
+            return Err(DataError::new(ErrorCode::SocialSecurityNumberLength, v));
         }
 
         let region_code = RegionCode::try_from(&clean[0..2])?;
 
         let day_of_birth = clean[2..4]
             .parse::<u8>()
-            .map_err(|_e| format!("Invalid day of birth: {}", v))?;
+            .map_err(|_e| DataError::new(ErrorCode::InvalidDayOfBirth, v))?;
 
         let month_of_birth = clean[4..6]
             .parse::<u8>()
-            .map_err(|_e| format!("Invalid month of birth: {}", v))?;
+            .map_err(|_e| DataError::new(ErrorCode::InvalidMonthOfBirth, v))?;
 
         let year_of_birth = clean[6..8]
             .parse::<u8>()
-            .map_err(|_e| format!("Invalid year of birth: {}", v))?;
+            .map_err(|_e| DataError::new(ErrorCode::InvalidYearOfBirth, v))?;
 
         // todo: check letter is between A..Z
         let first_letter_of_birth_name = clean.chars().nth(8).unwrap();
 
         let serial_number = clean[9..11]
             .parse::<u8>()
-            .map_err(|_e| format!("Invalid serial number: {}", v))?;
+            .map_err(|_e| DataError::new(ErrorCode::InvalidSerialNumber, v))?;
 
         let check_digit = clean[11..12]
             .parse::<u8>()
-            .map_err(|_e| format!("Invalid check digit: {}", v))?;
+            .map_err(|_e| DataError::new(ErrorCode::InvalidCheckDigit, v))?;
 
         // todo: validate check digit
         // https://de.wikipedia.org/wiki/Versicherungsnummer#Berechnung_der_Pr%C3%BCfziffer
@@ -208,6 +211,51 @@ impl Into<String> for SocialSecurityNumber {
     }
 }
 
+pub struct SocialSecurityNumberType;
+
+impl DocumentType for SocialSecurityNumberType {
+    fn id(&self) -> &'static str {
+        "social_security_number"
+    }
+
+    fn name(&self) -> &'static str {
+        "document-social-security-number"
+    }
+
+    fn fields(&self) -> Vec<FieldSpec> {
+        vec![FieldSpec::text("field-number")
+            .validate(|value| SocialSecurityNumber::try_from(value).map(|_| ()))]
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+
+    fn parse(&self, values: &[String]) -> Result<Document, DataError> {
+        let social_security_number =
+            SocialSecurityNumber::try_from(values.first().map(String::as_str).unwrap_or(""))?;
+
+        Ok(Document::new(
+            self.id(),
+            vec![social_security_number.to_string()],
+        ))
+    }
+
+    fn read(&self, profile: &Profile) -> Option<Document> {
+        profile
+            .social_security_number
+            .as_ref()
+            .map(|social_security_number| {
+                Document::new(self.id(), vec![social_security_number.to_string()])
+            })
+    }
+
+    fn write(&self, profile: &mut Profile, document: Option<Document>) {
+        profile.social_security_number =
+            document.and_then(|document| SocialSecurityNumber::try_from(document.value(0)).ok());
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
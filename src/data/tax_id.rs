@@ -1,3 +1,6 @@
+use super::document::{Document, DocumentType, FieldSpec};
+use super::error::{DataError, ErrorCode};
+use super::profile::Profile;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
@@ -11,35 +14,27 @@ const MIN_TAX_ID: u64 = 10_000_000_000;
 const MAX_TAX_ID: u64 = 99_999_999_999;
 
 impl TryFrom<u64> for TaxId {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(value: u64) -> Result<Self, Self::Error> {
         // todo: find correct constraints and enforce in here
-        if value < MIN_TAX_ID {
-            return Err(format!(
-                "Tax-ID to small: must have exactly 11 digits {}",
-                value
-            ));
-        } else if value > MAX_TAX_ID {
-            return Err(format!(
-                "Tax-ID to large: must have exactly 11 digits {}",
-                value
-            ));
-        } else {
-            Ok(TaxId { value })
+        if !(MIN_TAX_ID..=MAX_TAX_ID).contains(&value) {
+            return Err(DataError::new(ErrorCode::TaxIdLength, value.to_string()));
         }
+
+        Ok(TaxId { value })
     }
 }
 
 impl TryFrom<&str> for TaxId {
-    type Error = String;
+    type Error = DataError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let clean: String = value.split_whitespace().collect();
 
         match clean.parse::<u64>() {
             Ok(number) => TaxId::try_from(number),
-            Err(_) => Err(format!("Tax-ID invalid: {}", value)),
+            Err(_) => Err(DataError::new(ErrorCode::InvalidTaxId, value)),
         }
     }
 }
@@ -61,3 +56,38 @@ impl TaxId {
         return format!("{}", self.value);
     }
 }
+pub struct TaxIdType;
+
+impl DocumentType for TaxIdType {
+    fn id(&self) -> &'static str {
+        "tax_id"
+    }
+
+    fn name(&self) -> &'static str {
+        "document-tax-id"
+    }
+
+    fn fields(&self) -> Vec<FieldSpec> {
+        vec![FieldSpec::text("field-number").validate(|value| TaxId::try_from(value).map(|_| ()))]
+    }
+
+    fn is_secret(&self) -> bool {
+        true
+    }
+
+    fn parse(&self, values: &[String]) -> Result<Document, DataError> {
+        let tax_id = TaxId::try_from(values.first().map(String::as_str).unwrap_or(""))?;
+
+        Ok(Document::new(self.id(), vec![tax_id.to_string()]))
+    }
+
+    fn read(&self, profile: &Profile) -> Option<Document> {
+        profile
+            .tax_id
+            .map(|tax_id| Document::new(self.id(), vec![tax_id.to_string()]))
+    }
+
+    fn write(&self, profile: &mut Profile, document: Option<Document>) {
+        profile.tax_id = document.and_then(|document| TaxId::try_from(document.value(0)).ok());
+    }
+}
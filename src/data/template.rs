@@ -0,0 +1,334 @@
+//! User-defined templates for structured miscellaneous entries, e.g. an
+//! insurance policy with policy number, insurer and renewal date.
+//!
+//! Entry values reference the fields of their template by id and keep a
+//! copy of the label and kind, so entries stay readable when fields are
+//! renamed or removed from the template.
+
+use super::error::{DataError, ErrorCode};
+use super::ItemId;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateFieldKind {
+    Text,
+    Number,
+    /// Dates are stored as `2030-02-01`.
+    Date,
+    Iban,
+    Secret,
+}
+
+impl TemplateFieldKind {
+    pub const ALL: [TemplateFieldKind; 5] = [
+        TemplateFieldKind::Text,
+        TemplateFieldKind::Number,
+        TemplateFieldKind::Date,
+        TemplateFieldKind::Iban,
+        TemplateFieldKind::Secret,
+    ];
+
+    /// Id of the localized name.
+    pub fn name(self) -> &'static str {
+        match self {
+            TemplateFieldKind::Text => "field-kind-text",
+            TemplateFieldKind::Number => "field-kind-number",
+            TemplateFieldKind::Date => "field-kind-date",
+            TemplateFieldKind::Iban => "field-kind-iban",
+            TemplateFieldKind::Secret => "field-kind-secret",
+        }
+    }
+
+    /// The following kind in `ALL`, to switch through the kinds.
+    pub fn next(self) -> TemplateFieldKind {
+        let index = TemplateFieldKind::ALL
+            .iter()
+            .position(|kind| *kind == self)
+            .unwrap();
+
+        TemplateFieldKind::ALL[(index + 1) % TemplateFieldKind::ALL.len()]
+    }
+
+    /// Validate a non-empty value and bring it into its stored format.
+    pub fn parse(self, value: &str) -> Result<String, DataError> {
+        let value = value.trim();
+
+        match self {
+            TemplateFieldKind::Text | TemplateFieldKind::Secret => Ok(String::from(value)),
+            TemplateFieldKind::Number => value
+                .replace(',', ".")
+                .parse::<f64>()
+                .map(|_| String::from(value))
+                .map_err(|_| DataError::new(ErrorCode::InvalidNumber, value)),
+            TemplateFieldKind::Date => NaiveDate::from_str(value)
+                .map(|date| date.to_string())
+                .map_err(|_| DataError::new(ErrorCode::InvalidDate, value)),
+            TemplateFieldKind::Iban => parse_iban(value),
+        }
+    }
+}
+
+/// Validate the length and check digits of an IBAN and format it in
+/// groups of four characters.
+fn parse_iban(value: &str) -> Result<String, DataError> {
+    let clean: String = value.split_whitespace().collect::<String>().to_uppercase();
+
+    let is_well_formed = (15..=34).contains(&clean.len())
+        && clean.chars().all(|c| c.is_ascii_alphanumeric())
+        && clean.chars().take(2).all(|c| c.is_ascii_alphabetic())
+        && clean.chars().skip(2).take(2).all(|c| c.is_ascii_digit());
+    if !is_well_formed {
+        return Err(DataError::new(ErrorCode::InvalidIban, value));
+    }
+
+    let remainder = clean[4..]
+        .chars()
+        .chain(clean[..4].chars())
+        .map(|c| c.to_digit(36).unwrap())
+        .fold(0, |remainder, digit| {
+            let factor = if digit < 10 { 10 } else { 100 };
+            (remainder * factor + digit) % 97
+        });
+    if remainder != 1 {
+        return Err(DataError::new(ErrorCode::InvalidIbanCheckDigits, value));
+    }
+
+    let groups: Vec<String> = clean
+        .chars()
+        .collect::<Vec<char>>()
+        .chunks(4)
+        .map(|chunk| chunk.iter().collect())
+        .collect();
+
+    Ok(groups.join(" "))
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TemplateField {
+    pub id: u64,
+    pub label: String,
+    pub kind: TemplateFieldKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Template {
+    pub id: ItemId,
+    pub name: String,
+    pub fields: Vec<TemplateField>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct EntryValue {
+    pub field_id: u64,
+    pub label: String,
+    pub kind: TemplateFieldKind,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Entry {
+    pub id: ItemId,
+    pub template_id: ItemId,
+    pub name: String,
+    pub values: Vec<EntryValue>,
+}
+
+impl Entry {
+    /// Update the entry after its template was edited: values of existing
+    /// fields get the new label and the order of the template, values of
+    /// removed fields are kept at the end.
+    pub fn apply_template(&mut self, template: &Template) {
+        let mut values: Vec<EntryValue> = template
+            .fields
+            .iter()
+            .filter_map(|field| {
+                let value = self
+                    .values
+                    .iter()
+                    .find(|value| value.field_id == field.id)?;
+
+                Some(EntryValue {
+                    field_id: field.id,
+                    label: field.label.clone(),
+                    kind: value.kind,
+                    value: value.value.clone(),
+                })
+            })
+            .collect();
+
+        values.extend(
+            self.values
+                .iter()
+                .filter(|value| {
+                    !template
+                        .fields
+                        .iter()
+                        .any(|field| field.id == value.field_id)
+                })
+                .cloned(),
+        );
+
+        self.values = values;
+    }
+}
+
+/// An id, which is larger than all of `ids`.
+pub fn next_id(ids: impl Iterator<Item = u64>) -> u64 {
+    ids.max().map(|id| id + 1).unwrap_or(1)
+}
+
+/// The field ids, which the values of the entries of a template reference,
+/// including fields, which were removed from the template. New fields must
+/// not reuse them.
+pub fn used_field_ids<'a>(
+    template_id: ItemId,
+    entries: impl Iterator<Item = &'a Entry>,
+) -> Vec<u64> {
+    entries
+        .filter(|entry| entry.template_id == template_id)
+        .flat_map(|entry| entry.values.iter().map(|value| value.field_id))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_validate_and_format_ibans() {
+        assert_eq!(
+            TemplateFieldKind::Iban.parse("de89370400440532013000"),
+            Ok(String::from("DE89 3704 0044 0532 0130 00"))
+        );
+        assert!(TemplateFieldKind::Iban
+            .parse("DE88 3704 0044 0532 0130 00")
+            .is_err());
+        assert!(TemplateFieldKind::Iban.parse("DE89").is_err());
+    }
+
+    #[test]
+    fn should_validate_numbers_and_dates() {
+        assert_eq!(
+            TemplateFieldKind::Number.parse(" 12,50 "),
+            Ok(String::from("12,50"))
+        );
+        assert!(TemplateFieldKind::Number.parse("zwölf").is_err());
+        assert_eq!(
+            TemplateFieldKind::Date.parse("2030-02-01"),
+            Ok(String::from("2030-02-01"))
+        );
+        assert!(TemplateFieldKind::Date.parse("1.2.2030").is_err());
+    }
+
+    #[test]
+    fn should_cycle_through_the_field_kinds() {
+        assert_eq!(TemplateFieldKind::Text.next(), TemplateFieldKind::Number);
+        assert_eq!(TemplateFieldKind::Secret.next(), TemplateFieldKind::Text);
+    }
+
+    #[test]
+    fn should_keep_the_values_when_the_template_is_edited() {
+        let template_id = ItemId::new();
+        let mut entry = Entry {
+            id: ItemId::new(),
+            template_id,
+            name: "KFZ".into(),
+            values: vec![
+                create_value(1, "Versicherer", "HUK"),
+                create_value(2, "Nummer", "123"),
+            ],
+        };
+        let template = Template {
+            id: template_id,
+            name: "Versicherung".into(),
+            fields: vec![
+                TemplateField {
+                    id: 2,
+                    label: "Policennummer".into(),
+                    kind: TemplateFieldKind::Text,
+                },
+                TemplateField {
+                    id: 3,
+                    label: "Telefon".into(),
+                    kind: TemplateFieldKind::Text,
+                },
+            ],
+        };
+
+        entry.apply_template(&template);
+
+        assert_eq!(
+            entry.values,
+            vec![
+                create_value(2, "Policennummer", "123"),
+                create_value(1, "Versicherer", "HUK"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_create_increasing_ids() {
+        assert_eq!(next_id(vec![].into_iter()), 1);
+        assert_eq!(next_id(vec![3, 1].into_iter()), 4);
+    }
+
+    #[test]
+    fn should_not_reuse_the_ids_of_deleted_fields() {
+        let template = Template {
+            id: ItemId::new(),
+            name: "Versicherung".into(),
+            fields: vec![
+                TemplateField {
+                    id: 1,
+                    label: "Versicherer".into(),
+                    kind: TemplateFieldKind::Text,
+                },
+                TemplateField {
+                    id: 2,
+                    label: "Nummer".into(),
+                    kind: TemplateFieldKind::Text,
+                },
+            ],
+        };
+        let entry = Entry {
+            id: ItemId::new(),
+            template_id: template.id,
+            name: "KFZ".into(),
+            values: vec![
+                create_value(1, "Versicherer", "HUK"),
+                create_value(2, "Nummer", "123"),
+            ],
+        };
+
+        // The field is removed and the value is kept at the end.
+        let mut edited = template.clone();
+        edited.fields.pop();
+        let mut edited_entry = entry.clone();
+        edited_entry.apply_template(&edited);
+        let used = used_field_ids(template.id, [edited_entry.clone()].iter());
+        let field_id = next_id(edited.fields.iter().map(|field| field.id).chain(used));
+        assert_eq!(field_id, 3);
+
+        edited.fields.push(TemplateField {
+            id: field_id,
+            label: "Telefon".into(),
+            kind: TemplateFieldKind::Text,
+        });
+        let mut updated_entry = edited_entry.clone();
+        updated_entry.apply_template(&edited);
+
+        assert_eq!(updated_entry, entry);
+    }
+
+    fn create_value(field_id: u64, label: &str, value: &str) -> EntryValue {
+        EntryValue {
+            field_id,
+            label: label.into(),
+            kind: TemplateFieldKind::Text,
+            value: value.into(),
+        }
+    }
+}
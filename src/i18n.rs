@@ -0,0 +1,212 @@
+//! Translations of the UI with Fluent. The messages of each language are in
+//! `resources/locales/<language>/stammdaten.ftl` and compiled into the binary.
+//!
+//! The language is selected once in `main`. Ids without a message are shown
+//! as is, so names entered by the user can be passed through `text`.
+
+use crate::data::{BundleError, DataError, EventTexts, Expiry};
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Locale {
+    German,
+    English,
+}
+
+/// Used for unsupported languages.
+pub const DEFAULT_LOCALE: Locale = Locale::English;
+
+impl Locale {
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::German => "de-DE",
+            Locale::English => "en-US",
+        }
+    }
+
+    /// Find the locale of a language tag, e.g. `de`, `de-AT` or `de_DE.UTF-8`.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_', '.']).next()?.trim().to_lowercase();
+
+        match language.as_str() {
+            "de" => Some(Locale::German),
+            "en" => Some(Locale::English),
+            _ => None,
+        }
+    }
+
+    fn resource(self) -> &'static str {
+        match self {
+            Locale::German => include_str!("../resources/locales/de-DE/stammdaten.ftl"),
+            Locale::English => include_str!("../resources/locales/en-US/stammdaten.ftl"),
+        }
+    }
+}
+
+struct Localizer {
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    fn new(locale: Locale) -> Localizer {
+        let language: LanguageIdentifier = locale.tag().parse().expect("Invalid language tag");
+        let resource = FluentResource::try_new(String::from(locale.resource()))
+            .expect("Could not parse translations");
+
+        let mut bundle = FluentBundle::new_concurrent(vec![language]);
+        // The isolation marks around arguments are shown as boxes by druid.
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .expect("Could not add translations");
+
+        Localizer { bundle }
+    }
+
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        let pattern = match self
+            .bundle
+            .get_message(id)
+            .and_then(|message| message.value())
+        {
+            Some(pattern) => pattern,
+            None => return String::from(id),
+        };
+
+        let mut errors = Vec::new();
+        let text = self.bundle.format_pattern(pattern, args, &mut errors);
+        for error in errors {
+            log::warn!("Could not format message {}: {:?}", id, error);
+        }
+
+        text.into_owned()
+    }
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Select the language of the UI, before any text is shown.
+pub fn init(locale: Locale) {
+    if LOCALIZER.set(Localizer::new(locale)).is_err() {
+        log::warn!("The language of the UI was already selected");
+    }
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(DEFAULT_LOCALE))
+}
+
+pub fn text(id: &str) -> String {
+    localizer().format(id, None)
+}
+
+/// Message with arguments, e.g. `text_with("expiry-expired", &[("days", 3.into())])`.
+pub fn text_with<'a>(id: &str, args: &[(&'a str, FluentValue<'a>)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+
+    localizer().format(id, Some(&fluent_args))
+}
+
+pub fn error(error: &DataError) -> String {
+    text_with(
+        error.code.message_id(),
+        &[("value", error.value.as_str().into())],
+    )
+}
+
+pub fn bundle_error(error: BundleError) -> String {
+    text(error.message_id())
+}
+
+/// Texts of the exported calendar events in the language of the UI.
+pub struct CalendarTexts;
+
+impl EventTexts for CalendarTexts {
+    fn summary(&self, expiry: &Expiry) -> String {
+        text_with("calendar-summary", &[("name", text(expiry.name).into())])
+    }
+
+    fn description(&self, expiry: &Expiry) -> String {
+        text_with(
+            "calendar-description",
+            &[
+                ("name", text(expiry.name).into()),
+                ("reference", expiry.reference.as_str().into()),
+            ],
+        )
+    }
+
+    fn alarm(&self, expiry: &Expiry, days: i64) -> String {
+        text_with(
+            "calendar-alarm",
+            &[("name", text(expiry.name).into()), ("days", days.into())],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::data::ErrorCode;
+
+    /// Ids of the messages, which start at the beginning of a line.
+    fn message_ids(locale: Locale) -> Vec<&'static str> {
+        let mut ids: Vec<&str> = locale
+            .resource()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_alphabetic()))
+            .filter_map(|line| line.split_once('=').map(|(id, _)| id.trim()))
+            .collect();
+        ids.sort_unstable();
+
+        ids
+    }
+
+    #[test]
+    fn should_find_the_locale_of_a_language_tag() {
+        assert_eq!(Locale::from_tag("de-DE"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("de_AT.UTF-8"), Some(Locale::German));
+        assert_eq!(Locale::from_tag("en"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("fr-FR"), None);
+    }
+
+    #[test]
+    fn should_translate_all_messages() {
+        assert_eq!(message_ids(Locale::German), message_ids(Locale::English));
+    }
+
+    #[test]
+    fn should_format_messages() {
+        let localizer = Localizer::new(Locale::German);
+        let mut args = FluentArgs::new();
+        args.set("value", "123");
+
+        assert_eq!(localizer.format("save", None), "Speichern");
+        assert_eq!(
+            localizer.format(ErrorCode::TaxIdLength.message_id(), Some(&args)),
+            "Die Steuer-ID muss 11 Ziffern haben."
+        );
+        assert_eq!(
+            localizer.format("Mitgliedschaften", None),
+            "Mitgliedschaften"
+        );
+    }
+
+    #[test]
+    fn should_have_a_message_for_all_errors() {
+        let ids = message_ids(Locale::German);
+
+        for code in ErrorCode::ALL {
+            assert!(ids.contains(&code.message_id()), "{:?}", code);
+        }
+        for error in BundleError::ALL {
+            assert!(ids.contains(&error.message_id()), "{:?}", error);
+        }
+    }
+}
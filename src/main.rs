@@ -1,46 +1,96 @@
+mod cli;
+mod clipboard;
 mod data;
+mod i18n;
 mod state;
+mod theme;
 mod ui;
+mod watcher;
 mod widgets;
 
-use crate::data::Profile;
+use crate::data::{
+    Change, Profile, ReminderConfig, Settings, BUNDLE_EXTENSION, MIN_WINDOW_HEIGHT,
+    MIN_WINDOW_WIDTH,
+};
+use crate::i18n::Locale;
 use crate::state::AppState;
+use chrono::Utc;
 use directories::ProjectDirs;
 use druid::menu::Menu;
 use druid::{
-    platform_menus, AppDelegate, AppLauncher, Command, Data, DelegateCtx, Env, Handled,
-    LocalizedString, PlatformError, Target, WindowDesc,
+    commands, platform_menus, AppDelegate, AppLauncher, Application, Command, Data, DelegateCtx,
+    Env, Event, FileDialogOptions, FileSpec, Handled, LocalizedString, PlatformError, Target,
+    WindowDesc, WindowId,
 };
 use std::env;
-use std::fs::File;
-use std::path::PathBuf;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::process;
 
 const PROFILE_FILENAME: &str = "profile.json";
+const SETTINGS_FILENAME: &str = "settings.json";
+const HISTORY_FILENAME: &str = "history.jsonl";
+const CALENDAR_FILENAME: &str = "expiries.ics";
+const CALENDAR_FILE_TYPE: FileSpec = FileSpec::new("iCalendar", &["ics"]);
+const BUNDLE_FILENAME: &str = "Stammdaten.stammdaten";
+const BUNDLE_FILE_TYPE: FileSpec = FileSpec::new("Stammdaten", &[BUNDLE_EXTENSION]);
 
 fn main() -> Result<(), PlatformError> {
-    let profile_path = get_config_path().join(PROFILE_FILENAME);
-    let profile = match File::open(profile_path) {
+    let settings = get_settings();
+    i18n::init(get_locale(&settings));
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("merge") {
+        process::exit(cli::merge_profiles(&args[1..]));
+    }
+
+    let data_path = get_data_path(&settings);
+    let profile_path = data_path.join(PROFILE_FILENAME);
+    let (profile, is_migrated) = match File::open(&profile_path) {
         Ok(mut file) => {
-            Some(Profile::load_from_file(&mut file).expect("Could not load profile file"))
+            let (profile, is_migrated) =
+                Profile::load_migrated_from_file(&mut file).expect("Could not load profile file");
+            (Some(profile), is_migrated)
         }
-        Err(_) => None,
+        Err(_) => (None, false),
     };
 
-    let initial_state = match profile {
+    let history = load_history(&data_path);
+
+    let mut initial_state = match profile.clone() {
         Some(profile) => AppState::from_profile(profile),
         None => AppState::new(),
     };
+    initial_state.add_changes(history);
+
+    let mut window = WindowDesc::new(ui::build_ui(settings.theme))
+        .title("Stammdaten")
+        .menu(|_, _, _| app_menu())
+        .window_size(settings.window_size())
+        .with_min_size((MIN_WINDOW_WIDTH, MIN_WINDOW_HEIGHT));
+    if let Some(position) = settings.window_position() {
+        window = window.set_position(position);
+    }
+
+    let mut delegate = Delegate {
+        reminders: settings.reminders(),
+        data_path,
+        profile_base: profile.clone(),
+        calendar_export: None,
+        bundle_export: None,
+        bundle_import: None,
+    };
+    // Keep the ids, which the items of an older profile got when loaded.
+    if let (Some(profile), true) = (profile, is_migrated) {
+        delegate.save_profile(&profile, &mut initial_state);
+    }
+
+    let launcher = AppLauncher::with_window(window)
+        .delegate(delegate)
+        .configure_env(move |env, _| ui::configure_env(env, &settings));
+    watcher::watch_profile(profile_path, launcher.get_external_handle());
 
-    AppLauncher::with_window(
-        WindowDesc::new(ui::build_ui())
-            .title("Stammdaten")
-            .menu(|_, _, _| app_menu())
-            .window_size((800.0, 600.0))
-            .resizable(false),
-    )
-    .delegate(Delegate)
-    .log_to_console()
-    .launch(initial_state)?;
+    launcher.log_to_console().launch(initial_state)?;
     Ok(())
 }
 
@@ -64,6 +114,8 @@ fn edit_menu<T: Data>() -> Menu<T> {
         .entry(platform_menus::common::paste())
 }
 
+/// Directory of the settings and, unless configured otherwise, of the
+/// profile.
 fn get_config_path() -> PathBuf {
     match env::var("STAMMDATEN_DATA_DIR") {
         Ok(path) => PathBuf::from(path),
@@ -76,33 +128,358 @@ fn get_config_path() -> PathBuf {
     }
 }
 
+/// Directory of the profile. `STAMMDATEN_DATA_DIR` takes precedence over the
+/// directory in the settings.
+fn get_data_path(settings: &Settings) -> PathBuf {
+    match (env::var("STAMMDATEN_DATA_DIR"), settings.data_dir.trim()) {
+        (Ok(path), _) => PathBuf::from(path),
+        (Err(_), "") => get_config_path(),
+        (Err(_), path) => PathBuf::from(path),
+    }
+}
+
+/// The changes in `history.jsonl`, the oldest first.
+fn load_history(data_path: &Path) -> Vec<Change> {
+    match File::open(data_path.join(HISTORY_FILENAME)) {
+        Ok(mut file) => data::load_history(&mut file).expect("Could not load history file"),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// The settings as stored in `settings.json`, the defaults if they cannot
+/// be loaded.
+fn load_settings() -> Settings {
+    let settings_path = get_config_path().join(SETTINGS_FILENAME);
+    match File::open(settings_path).map(|mut file| Settings::load_from_file(&mut file)) {
+        Ok(Ok(settings)) => settings,
+        Ok(Err(error)) => {
+            log::warn!("Could not load settings file: {}", error);
+            Settings::default()
+        }
+        Err(_) => Settings::default(),
+    }
+}
+
+fn save_settings(settings: &Settings) {
+    let config_path = get_config_path();
+    std::fs::create_dir_all(&config_path).expect("Could not create config directory");
+
+    let mut file = File::create(config_path.join(SETTINGS_FILENAME))
+        .expect("Could not open file to save settings");
+    settings
+        .save_to_file(&mut file)
+        .expect("Could not save settings");
+}
+
+/// The settings from `settings.json`. The language can be overridden with
+/// e.g. `STAMMDATEN_LANGUAGE=de`, the lead times in days for expiry
+/// reminders with e.g. `STAMMDATEN_REMINDER_DAYS=90,30,7` and the seconds
+/// until copied values are cleared with e.g. `STAMMDATEN_CLIPBOARD_TIMEOUT=0`.
+fn get_settings() -> Settings {
+    let mut settings = load_settings();
+
+    if let Ok(language) = env::var("STAMMDATEN_LANGUAGE") {
+        settings.language = language;
+    }
+    if let Ok(value) = env::var("STAMMDATEN_REMINDER_DAYS") {
+        match value.parse::<ReminderConfig>() {
+            Ok(reminders) => settings.set_reminders(&reminders),
+            Err(error) => log::warn!("Could not parse STAMMDATEN_REMINDER_DAYS: {}", error),
+        }
+    }
+    if let Ok(value) = env::var("STAMMDATEN_CLIPBOARD_TIMEOUT") {
+        match value.parse() {
+            Ok(timeout) => settings.clipboard_timeout = timeout,
+            Err(error) => log::warn!("Could not parse STAMMDATEN_CLIPBOARD_TIMEOUT: {}", error),
+        }
+    }
+
+    settings
+}
+
+/// Language of the UI from the settings, or the language of the system.
+fn get_locale(settings: &Settings) -> Locale {
+    let tag = match settings.language.trim() {
+        "" => Application::get_locale(),
+        language => String::from(language),
+    };
+
+    Locale::from_tag(&tag).unwrap_or(i18n::DEFAULT_LOCALE)
+}
+
 /// Global command handler.
-struct Delegate;
+struct Delegate {
+    reminders: ReminderConfig,
+    /// Directory of the profile, its history and the calendar.
+    data_path: PathBuf,
+    /// The profile, as it was last loaded or saved. Changes of other
+    /// programs are detected by comparing the file with it.
+    profile_base: Option<Profile>,
+    /// Profile to export, while the save panel for the calendar is open.
+    calendar_export: Option<Profile>,
+    /// Passphrase, while the save panel for the bundle is open.
+    bundle_export: Option<String>,
+    /// Passphrase, while the open panel for the bundle is open.
+    bundle_import: Option<String>,
+}
+
+impl Delegate {
+    fn save_calendar(&self, profile: &Profile, path: &Path) {
+        let calendar = data::to_icalendar(
+            &profile.expiries(),
+            &self.reminders.lead_times,
+            &i18n::CalendarTexts,
+            Utc::now(),
+        );
+
+        if let Err(error) = std::fs::write(path, calendar) {
+            log::error!("Could not save calendar: {}", error);
+        }
+    }
+
+    /// The profile on disk and whether it was migrated, see
+    /// `Profile::load_migrated_from_file`. `None` if there is none, or if it
+    /// cannot be parsed, e.g. while a sync client writes it.
+    fn read_profile(&self) -> Option<(Profile, bool)> {
+        let mut file = File::open(self.data_path.join(PROFILE_FILENAME)).ok()?;
+
+        match Profile::load_migrated_from_file(&mut file) {
+            Ok(profile) => Some(profile),
+            Err(error) => {
+                log::warn!("Could not load the changed profile: {}", error);
+                None
+            }
+        }
+    }
+
+    /// Save the profile and record its changes since it was last loaded
+    /// or saved.
+    fn save_profile(&mut self, profile: &Profile, data: &mut AppState) {
+        std::fs::create_dir_all(&self.data_path).expect("Could not create data directory");
+
+        if let Some(base) = &self.profile_base {
+            let changes = data::changes(base, profile, Utc::now());
+            if !changes.is_empty() {
+                self.save_history(&changes);
+                data.add_changes(changes);
+            }
+        }
+
+        let profile_path = self.data_path.join(PROFILE_FILENAME);
+        let mut file = File::create(profile_path).expect("Could not open file to save profile");
+        profile
+            .save_to_file(&mut file)
+            .expect("Could not save profile");
+
+        self.save_calendar(profile, &self.data_path.join(CALENDAR_FILENAME));
+        self.profile_base = Some(profile.clone());
+    }
+
+    /// Append the changes to the history next to the profile.
+    fn save_history(&self, changes: &[Change]) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.data_path.join(HISTORY_FILENAME))
+            .expect("Could not open file to save history");
+        data::append_history(&mut file, changes).expect("Could not save history");
+    }
+
+    /// Save all files in the data directory as bundle. The settings are not
+    /// exported, because they depend on the machine, e.g. the data
+    /// directory.
+    fn export_bundle(&self, passphrase: &str, path: &Path) -> Result<(), String> {
+        let mut files = data::read_directory(&self.data_path).map_err(|error| {
+            i18n::text_with("bundle-error-read", &[("error", error.to_string().into())])
+        })?;
+        let bundle_suffix = format!(".{}", BUNDLE_EXTENSION);
+        files.retain(|file| file.name != SETTINGS_FILENAME && !file.name.ends_with(&bundle_suffix));
+
+        std::fs::write(path, data::create_bundle(files, passphrase)).map_err(|error| {
+            i18n::text_with("bundle-error-write", &[("error", error.to_string().into())])
+        })
+    }
+
+    /// Restore the files of a bundle into the data directory, once it was
+    /// verified, and take its profile.
+    fn import_bundle(
+        &mut self,
+        passphrase: &str,
+        path: &Path,
+        state: &mut AppState,
+    ) -> Result<(), String> {
+        let bundle = std::fs::read(path).map_err(|error| {
+            i18n::text_with("bundle-error-read", &[("error", error.to_string().into())])
+        })?;
+        let files = data::open_bundle(&bundle, passphrase).map_err(i18n::bundle_error)?;
+        let profile =
+            data::bundled_profile(&files, PROFILE_FILENAME).map_err(i18n::bundle_error)?;
+
+        data::write_directory(&self.data_path, &files).map_err(|error| {
+            i18n::text_with("bundle-error-write", &[("error", error.to_string().into())])
+        })?;
+
+        *state = AppState::from_profile(profile.clone());
+        state.add_changes(load_history(&self.data_path));
+        self.profile_base = Some(profile);
+
+        Ok(())
+    }
+
+    /// Take the profile from disk, if another program changed it. Changes
+    /// in the app are merged and saved, unless they conflict. Returns
+    /// whether the profile on disk was changed.
+    fn reload_profile(&mut self, data: &mut AppState) -> bool {
+        let (remote, is_migrated) = match self.read_profile() {
+            Some((profile, is_migrated)) if Some(&profile) != self.profile_base.as_ref() => {
+                (profile, is_migrated)
+            }
+            _ => return false,
+        };
+
+        let merged = data.reload_profile(self.profile_base.as_ref(), remote.clone());
+        self.profile_base = Some(remote.clone());
+        match merged {
+            Some(merged) => self.save_profile(&merged, data),
+            // Otherwise the items of an older profile get other ids, each
+            // time it is loaded, and it seems to be changed again.
+            None if is_migrated => self.save_profile(&remote, data),
+            None => {}
+        }
+
+        true
+    }
+}
 
 impl AppDelegate<AppState> for Delegate {
+    /// Handle global shortcuts here, because key events only reach the
+    /// focused widget.
+    fn event(
+        &mut self,
+        ctx: &mut DelegateCtx,
+        window_id: WindowId,
+        event: Event,
+        _data: &mut AppState,
+        _env: &Env,
+    ) -> Option<Event> {
+        if let Event::KeyDown(key) = &event {
+            if let Some(command) = ui::shortcut_command(key) {
+                ctx.submit_command(command.to(window_id));
+                return None;
+            }
+        }
+
+        Some(event)
+    }
+
     fn command(
         &mut self,
-        _ctx: &mut DelegateCtx,
-        _target: Target,
+        ctx: &mut DelegateCtx,
+        target: Target,
         cmd: &Command,
-        _data: &mut AppState,
+        data: &mut AppState,
         _env: &Env,
     ) -> Handled {
+        if cmd.is(watcher::PROFILE_FILE_CHANGED) {
+            self.reload_profile(data);
+
+            return Handled::Yes;
+        }
+
         if cmd.is(ui::SAVE_PROFILE) {
-            let config_path = get_config_path();
-            std::fs::create_dir_all(&config_path).expect("Could not create data directory");
+            // Changes of other programs are not overwritten, but merged
+            // with the changes in the app.
+            if !self.reload_profile(data) {
+                self.save_profile(cmd.get_unchecked(ui::SAVE_PROFILE), data);
+            }
 
-            let profile_path = config_path.join(PROFILE_FILENAME);
-            let profile = cmd.get_unchecked(ui::SAVE_PROFILE);
+            return Handled::Yes;
+        }
 
-            let mut file = File::create(profile_path).expect("Could not open file to save profile");
-            profile
-                .save_to_file(&mut file)
-                .expect("Could not save profile");
+        if cmd.is(ui::SAVE_SETTINGS) {
+            // The settings page does not change the window.
+            let stored_settings = load_settings();
+            save_settings(&Settings {
+                window_width: stored_settings.window_width,
+                window_height: stored_settings.window_height,
+                window_x: stored_settings.window_x,
+                window_y: stored_settings.window_y,
+                ..cmd.get_unchecked(ui::SAVE_SETTINGS).clone()
+            });
 
             return Handled::Yes;
         }
 
+        if cmd.is(ui::SAVE_WINDOW) {
+            let (size, position) = cmd.get_unchecked(ui::SAVE_WINDOW);
+
+            // Only the window is changed, not the settings overridden by
+            // environment variables.
+            let mut settings = load_settings();
+            settings.set_window((size.width, size.height), (position.x, position.y));
+            save_settings(&settings);
+
+            return Handled::Yes;
+        }
+
+        if cmd.is(ui::EXPORT_CALENDAR) {
+            self.calendar_export = Some(cmd.get_unchecked(ui::EXPORT_CALENDAR).clone());
+            self.bundle_export = None;
+
+            let options = FileDialogOptions::new()
+                .allowed_types(vec![CALENDAR_FILE_TYPE])
+                .default_type(CALENDAR_FILE_TYPE)
+                .default_name(CALENDAR_FILENAME);
+            ctx.submit_command(commands::SHOW_SAVE_PANEL.with(options).to(target));
+
+            return Handled::Yes;
+        }
+
+        if cmd.is(ui::EXPORT_BUNDLE) {
+            self.bundle_export = Some(cmd.get_unchecked(ui::EXPORT_BUNDLE).clone());
+            self.calendar_export = None;
+
+            let options = FileDialogOptions::new()
+                .allowed_types(vec![BUNDLE_FILE_TYPE])
+                .default_type(BUNDLE_FILE_TYPE)
+                .default_name(BUNDLE_FILENAME);
+            ctx.submit_command(commands::SHOW_SAVE_PANEL.with(options).to(target));
+
+            return Handled::Yes;
+        }
+
+        if let Some(file_info) = cmd.get(commands::SAVE_FILE_AS) {
+            if let Some(profile) = self.calendar_export.take() {
+                self.save_calendar(&profile, file_info.path());
+
+                return Handled::Yes;
+            }
+            if let Some(passphrase) = self.bundle_export.take() {
+                data.set_export_result(self.export_bundle(&passphrase, file_info.path()));
+
+                return Handled::Yes;
+            }
+        }
+
+        if cmd.is(ui::IMPORT_BUNDLE) {
+            self.bundle_import = Some(cmd.get_unchecked(ui::IMPORT_BUNDLE).clone());
+
+            let options = FileDialogOptions::new().allowed_types(vec![BUNDLE_FILE_TYPE]);
+            ctx.submit_command(commands::SHOW_OPEN_PANEL.with(options).to(target));
+
+            return Handled::Yes;
+        }
+
+        if let Some(file_info) = cmd.get(commands::OPEN_FILE) {
+            if let Some(passphrase) = self.bundle_import.take() {
+                if let Err(error) = self.import_bundle(&passphrase, file_info.path(), data) {
+                    data.set_import_error(error);
+                }
+
+                return Handled::Yes;
+            }
+        }
+
         return Handled::No;
     }
 }
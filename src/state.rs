@@ -5,16 +5,22 @@
 //! the ser/de and the ui modules.
 
 use crate::data::{
-    BankAccount, IdCard, KeyValueItem, Name, PostNumber, Profile, SocialSecurityNumber, TaxId,
-};
-use crate::ui::{
-    create_bank_account, create_id_card, create_key_value_item, create_post_number, create_profile,
-    create_social_security_number, create_tax_id,
+    document_type, merge, move_item, pinned_first, toggle_pin, BankAccount, Change, Conflict,
+    DateFormat, Document, Entry, EntryValue, Expiry, ItemId, KeyValueItem, Name, Profile,
+    ProfileField, SearchItem, Template, TemplateFieldKind, Theme,
 };
+use crate::i18n;
+use crate::ui::create::Process;
+use crate::ui::create_profile;
+use crate::ui::form::FormState;
+use crate::ui::settings::SettingsState;
+use crate::ui::template_editor;
+use crate::widgets::ConfirmState;
 use druid::im::Vector;
 use druid::{Data, Lens};
 use druid_enums::Matcher;
 use std::convert::From;
+use std::sync::Arc;
 
 /// Macro to impl the `Data` trait for structs with the `Eq` trait.
 macro_rules! impl_data_simple {
@@ -28,55 +34,241 @@ macro_rules! impl_data_simple {
 }
 
 impl_data_simple!(Name);
-impl_data_simple!(IdCard);
-impl_data_simple!(SocialSecurityNumber);
-impl_data_simple!(TaxId);
-impl_data_simple!(PostNumber);
+impl_data_simple!(Document);
 impl_data_simple!(BankAccount);
 impl_data_simple!(KeyValueItem);
+impl_data_simple!(Template);
+impl_data_simple!(TemplateFieldKind);
+impl_data_simple!(Entry);
+impl_data_simple!(EntryValue);
+impl_data_simple!(SearchItem);
+impl_data_simple!(ItemId);
+impl_data_simple!(DateFormat);
+impl_data_simple!(Theme);
+impl_data_simple!(ProfileField);
+impl_data_simple!(Change);
 
 #[derive(Clone, Copy, Data, PartialEq, Eq, Debug)]
 pub enum Nav {
     Home,
     BankAccounts,
     Miscellaneous,
+    History,
+    Settings,
+}
+
+/// Something to delete from the profile.
+#[derive(Clone, Data, PartialEq, Eq, Debug)]
+pub enum Deletion {
+    /// The document of the given type.
+    Document(&'static str),
+    BankAccount(ItemId),
+    KeyValueItem(ItemId),
+    Template(ItemId),
+    Entry(ItemId),
 }
 
 #[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
 pub struct ProfileState {
     pub name: Name,
-    pub id_card: Option<IdCard>,
-    pub social_security_number: Option<SocialSecurityNumber>,
-    pub tax_id: Option<TaxId>,
-    pub post_number: Option<PostNumber>,
+    pub documents: Vector<Document>,
     pub bank_accounts: Vector<BankAccount>,
     pub key_value_items: Vector<KeyValueItem>,
+    pub templates: Vector<Template>,
+    pub entries: Vector<Entry>,
 }
 
 impl ProfileState {
     pub fn get_profile(&self) -> Profile {
-        Profile {
-            name: self.name.clone(),
-            id_card: self.id_card.clone(),
-            social_security_number: self.social_security_number.clone(),
-            tax_id: self.tax_id.clone(),
-            post_number: self.post_number.clone(),
-            bank_accounts: self.bank_accounts.clone().into_iter().collect(),
-            key_value_items: self.key_value_items.clone().into_iter().collect(),
+        let mut profile = Profile::new(self.name.first_name.clone(), self.name.last_name.clone());
+        profile.set_documents(self.documents.clone().into_iter().collect());
+        profile.bank_accounts = self.bank_accounts.clone().into_iter().collect();
+        profile.key_value_items = self.key_value_items.clone().into_iter().collect();
+        profile.templates = self.templates.clone().into_iter().collect();
+        profile.entries = self.entries.clone().into_iter().collect();
+
+        profile
+    }
+
+    pub fn document(&self, type_id: &str) -> Option<Document> {
+        self.documents
+            .iter()
+            .find(|document| document.type_id == type_id)
+            .cloned()
+    }
+
+    /// Add, replace or remove the document of the given type.
+    pub fn set_document(&mut self, type_id: &str, document: Option<Document>) {
+        if self.document(type_id) == document {
+            return;
         }
+
+        let index = self
+            .documents
+            .iter()
+            .position(|document| document.type_id == type_id);
+
+        match (index, document) {
+            (Some(index), Some(document)) => {
+                self.documents.set(index, document);
+            }
+            (Some(index), None) => {
+                self.documents.remove(index);
+            }
+            (None, Some(document)) => self.documents.push_back(document),
+            (None, None) => {}
+        }
+    }
+
+    /// All expiration dates of the documents in this profile, the most
+    /// urgent first.
+    pub fn expiries(&self) -> Vec<Expiry> {
+        let mut expiries: Vec<Expiry> =
+            self.documents.iter().filter_map(Document::expiry).collect();
+        expiries.sort_by_key(|expiry| expiry.valid_until);
+
+        expiries
+    }
+
+    /// Describe what is deleted, to confirm the deletion.
+    pub fn describe(&self, deletion: &Deletion) -> String {
+        match deletion {
+            Deletion::Document(type_id) => {
+                let name =
+                    document_type(type_id).map_or(*type_id, |document_type| document_type.name());
+                i18n::text_with("delete-document", &[("name", i18n::text(name).into())])
+            }
+            Deletion::BankAccount(id) => {
+                let name = self
+                    .bank_accounts
+                    .iter()
+                    .find(|account| account.id == *id)
+                    .map_or("", |account| account.name.as_str());
+                i18n::text_with("delete-bank-account", &[("name", name.into())])
+            }
+            Deletion::KeyValueItem(id) => {
+                let key = self
+                    .key_value_items
+                    .iter()
+                    .find(|item| item.id == *id)
+                    .map_or("", |item| item.key.as_str());
+                i18n::text_with("delete-item", &[("name", key.into())])
+            }
+            Deletion::Template(id) => {
+                let name = self
+                    .templates
+                    .iter()
+                    .find(|template| template.id == *id)
+                    .map_or("", |template| template.name.as_str());
+                let has_entries = self.entries.iter().any(|entry| entry.template_id == *id);
+                let id = match has_entries {
+                    true => "delete-template-with-entries",
+                    false => "delete-template",
+                };
+                i18n::text_with(id, &[("name", name.into())])
+            }
+            Deletion::Entry(id) => {
+                let name = self
+                    .entries
+                    .iter()
+                    .find(|entry| entry.id == *id)
+                    .map_or("", |entry| entry.name.as_str());
+                i18n::text_with("delete-item", &[("name", name.into())])
+            }
+        }
+    }
+
+    pub fn delete(&mut self, deletion: &Deletion) {
+        match deletion {
+            Deletion::Document(type_id) => self.set_document(type_id, None),
+            Deletion::BankAccount(id) => self.bank_accounts.retain(|account| account.id != *id),
+            Deletion::KeyValueItem(id) => self.key_value_items.retain(|item| item.id != *id),
+            Deletion::Template(id) => self.templates.retain(|template| template.id != *id),
+            Deletion::Entry(id) => self.entries.retain(|entry| entry.id != *id),
+        }
+    }
+
+    /// Move the bank account or item by `steps`. Returns whether it was
+    /// moved.
+    pub fn move_item(&mut self, id: ItemId, steps: isize) -> bool {
+        reorder(&mut self.bank_accounts, |accounts| {
+            move_item(accounts, id, steps)
+        }) || reorder(&mut self.key_value_items, |items| {
+            move_item(items, id, steps)
+        })
+    }
+
+    /// Pin or unpin the bank account or item.
+    pub fn toggle_pin(&mut self, id: ItemId) -> bool {
+        reorder(&mut self.bank_accounts, |accounts| toggle_pin(accounts, id))
+            || reorder(&mut self.key_value_items, |items| toggle_pin(items, id))
+    }
+
+    /// All values of this profile, which can be found in the search.
+    pub fn search_items(&self) -> Vec<SearchItem> {
+        let mut items = vec![SearchItem::new(
+            i18n::text("field-name"),
+            format!("{} {}", self.name.first_name, self.name.last_name),
+            false,
+        )];
+
+        items.extend(self.documents.iter().filter_map(|document| {
+            let document_type = document.document_type()?;
+
+            Some(SearchItem::new(
+                i18n::text(document_type.name()),
+                document_type.copy_value(document),
+                document_type.is_secret(),
+            ))
+        }));
+        items.extend(
+            self.bank_accounts
+                .iter()
+                .map(|account| SearchItem::new(&account.name, &account.iban, true)),
+        );
+        items.extend(
+            self.key_value_items
+                .iter()
+                .map(|item| SearchItem::new(&item.key, &item.value, item.secret)),
+        );
+        items.extend(self.entries.iter().flat_map(|entry| {
+            entry.values.iter().map(move |value| {
+                SearchItem::new(
+                    format!("{}: {}", entry.name, value.label),
+                    &value.value,
+                    value.kind == TemplateFieldKind::Secret,
+                )
+            })
+        }));
+
+        items
     }
 }
 
+/// Change the order of `items`, if `f` returns true.
+fn reorder<T: Clone>(items: &mut Vector<T>, f: impl FnOnce(&mut Vec<T>) -> bool) -> bool {
+    let mut reordered: Vec<T> = items.iter().cloned().collect();
+    let changed = f(&mut reordered);
+    if changed {
+        *items = reordered.into_iter().collect();
+    }
+
+    changed
+}
+
 impl From<Profile> for ProfileState {
-    fn from(profile: Profile) -> ProfileState {
+    fn from(mut profile: Profile) -> ProfileState {
+        // Pinned items come first, even if the file was edited by hand.
+        pinned_first(&mut profile.bank_accounts);
+        pinned_first(&mut profile.key_value_items);
+
         ProfileState {
+            documents: profile.documents().into_iter().collect(),
             name: profile.name,
-            id_card: profile.id_card,
-            social_security_number: profile.social_security_number,
-            tax_id: profile.tax_id,
-            post_number: profile.post_number,
             bank_accounts: profile.bank_accounts.into_iter().collect(),
             key_value_items: profile.key_value_items.into_iter().collect(),
+            templates: profile.templates.into_iter().collect(),
+            entries: profile.entries.into_iter().collect(),
         }
     }
 }
@@ -85,42 +277,230 @@ impl From<Profile> for ProfileState {
 pub struct HomeState {
     pub profile: ProfileState,
     pub nav: Nav,
+    /// The search palette, while it is open.
+    pub palette: Option<PaletteState>,
+    /// The deletion, while it waits for the confirmation.
+    pub confirmation: Option<Confirmation>,
+    /// Whether deletions are confirmed, until the user chooses to not be
+    /// asked again in this session.
+    pub confirm_deletions: bool,
+    pub miscellaneous: MiscellaneousState,
+    pub history: HistoryState,
+    /// The settings page, once it was opened.
+    pub settings: Option<SettingsState>,
+}
+
+/// Sections and filter of the "Sonstiges" page.
+#[derive(Clone, Data, Lens, PartialEq, Eq, Default, Debug)]
+pub struct MiscellaneousState {
+    /// Categories, whose items are hidden.
+    pub collapsed: Vector<String>,
+    /// Only the items with this tag are shown.
+    pub tag_filter: Option<String>,
+}
+
+/// Changes and filter of the "Verlauf" page.
+#[derive(Clone, Data, Lens, PartialEq, Eq, Default, Debug)]
+pub struct HistoryState {
+    /// All recorded changes, the oldest first.
+    pub changes: Vector<Change>,
+    /// Only the changes of this field are shown.
+    pub filter: Option<ProfileField>,
+}
+
+#[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
+pub struct Confirmation {
+    pub deletion: Deletion,
+    pub dialog: ConfirmState,
+}
+
+#[derive(Clone, Data, Lens, PartialEq, Eq, Default, Debug)]
+pub struct PaletteState {
+    pub query: Arc<String>,
+    /// Index of the selected search result.
+    pub selected: usize,
+}
+
+#[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
+pub struct ProcessState {
+    pub home_state: HomeState,
+    pub process: Process,
+    pub form_state: FormState,
+}
+
+impl ProcessState {
+    /// `None`, if the process has no form, see `Process::form`.
+    pub fn new(home_state: HomeState, process: Process) -> Option<ProcessState> {
+        Some(ProcessState {
+            form_state: FormState::new(&process.form(&home_state.profile)?),
+            home_state,
+            process,
+        })
+    }
 }
 
 #[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
-pub struct ProcessState<F> {
+pub struct TemplateEditorState {
     pub home_state: HomeState,
-    pub form_state: F,
+    pub form_state: template_editor::FormState,
 }
 
-impl<F> From<HomeState> for ProcessState<F>
-where
-    F: Default,
-{
-    fn from(home_state: HomeState) -> ProcessState<F> {
-        ProcessState {
+impl TemplateEditorState {
+    /// Edit the template with the given id or create a new one.
+    pub fn new(home_state: HomeState, template_id: Option<ItemId>) -> TemplateEditorState {
+        let template = home_state
+            .profile
+            .templates
+            .iter()
+            .find(|template| Some(template.id) == template_id);
+
+        TemplateEditorState {
+            form_state: template_editor::FormState::new(
+                template,
+                home_state.profile.entries.iter(),
+            ),
             home_state,
-            form_state: F::default(),
         }
     }
 }
 
-pub type CreatePostNumberState = ProcessState<create_post_number::FormState>;
-pub type CreateTaxIdState = ProcessState<create_tax_id::FormState>;
-pub type CreateIdCardState = ProcessState<create_id_card::FormState>;
-pub type CreateSocialSecurityNumberState = ProcessState<create_social_security_number::FormState>;
-pub type CreateBankAccountState = ProcessState<create_bank_account::FormState>;
-pub type CreateKeyValueItemState = ProcessState<create_key_value_item::FormState>;
+/// How the user resolves a conflict with the profile on disk.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Resolution {
+    KeepMine,
+    TakeTheirs,
+    /// Take the fields from disk, which the user selected.
+    Merge,
+}
+
+/// A field, which was changed differently in the app and on disk.
+#[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
+pub struct FieldChoice {
+    pub field: ProfileField,
+    pub label: String,
+    pub local: String,
+    pub remote: String,
+    pub is_secret: bool,
+    /// Whether the value from disk is taken, when merging.
+    pub take_remote: bool,
+}
+
+impl From<Conflict> for FieldChoice {
+    fn from(conflict: Conflict) -> FieldChoice {
+        let describe =
+            |value: Option<String>| value.unwrap_or_else(|| i18n::text("conflict-empty"));
+
+        FieldChoice {
+            label: field_label(&conflict.field),
+            local: describe(conflict.local),
+            remote: describe(conflict.remote),
+            is_secret: conflict.is_secret,
+            take_remote: false,
+            field: conflict.field,
+        }
+    }
+}
+
+/// Name of a field, e.g. to list the conflicts.
+pub fn field_label(field: &ProfileField) -> String {
+    match field {
+        ProfileField::Name => i18n::text("field-name"),
+        ProfileField::Document(type_id) => document_type(type_id)
+            .map_or(type_id.clone(), |document_type| {
+                i18n::text(document_type.name())
+            }),
+        // The IBAN is part of the masked value.
+        ProfileField::BankAccount(_) => i18n::text("nav-bank-accounts"),
+        ProfileField::KeyValueItem(key) => {
+            format!("{}: {}", i18n::text("nav-miscellaneous"), key)
+        }
+        ProfileField::Template(_) => i18n::text("templates"),
+        ProfileField::Entry(_) => i18n::text("entries"),
+    }
+}
+
+/// The profile was changed on disk, while it also had changes in the app.
+/// The changes, which do not conflict, are already merged.
+#[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
+pub struct ConflictState {
+    /// The state with the profile of the app, to which the app returns.
+    pub home_state: HomeState,
+    /// The profile, as it was loaded or saved before both changes.
+    pub base: ProfileState,
+    /// The profile, as it is on disk.
+    pub remote: ProfileState,
+    /// Both changes with the value of the app for each conflict.
+    pub merged: ProfileState,
+    pub fields: Vector<FieldChoice>,
+}
+
+impl ConflictState {
+    pub fn new(home_state: HomeState, base: Profile, remote: Profile) -> ConflictState {
+        let remote = ProfileState::from(remote);
+        let merge = merge(
+            &base,
+            &home_state.profile.get_profile(),
+            &remote.get_profile(),
+        );
+
+        ConflictState {
+            base: ProfileState::from(base),
+            merged: ProfileState::from(merge.profile),
+            fields: merge.conflicts.into_iter().map(FieldChoice::from).collect(),
+            home_state,
+            remote,
+        }
+    }
+
+    /// The home state with the resolved profile.
+    pub fn resolve(&self, resolution: Resolution) -> HomeState {
+        let profile = match resolution {
+            Resolution::KeepMine => self.home_state.profile.clone(),
+            Resolution::TakeTheirs => self.remote.clone(),
+            Resolution::Merge => {
+                let remote = self.remote.get_profile();
+                let mut profile = self.merged.get_profile();
+                for choice in self.fields.iter().filter(|choice| choice.take_remote) {
+                    profile.copy_field(&remote, &choice.field);
+                }
+                ProfileState::from(profile)
+            }
+        };
+
+        let mut home_state = self.home_state.clone();
+        home_state.profile = profile;
+        home_state
+    }
+}
 
 #[derive(Clone, PartialEq, Eq, Debug, Data, Matcher)]
 pub enum MainState {
     Home(HomeState),
-    CreateSocialSecurityNumber(CreateSocialSecurityNumberState),
-    CreateTaxId(CreateTaxIdState),
-    CreatePostNumber(CreatePostNumberState),
-    CreateIdCard(CreateIdCardState),
-    CreateBankAccount(CreateBankAccountState),
-    CreateKeyValueItem(CreateKeyValueItemState),
+    Process(ProcessState),
+    TemplateEditor(TemplateEditorState),
+    Conflict(ConflictState),
+}
+
+impl MainState {
+    /// The state of the home screen, also while a form is open.
+    pub fn home_state_mut(&mut self) -> &mut HomeState {
+        match self {
+            MainState::Home(state) => state,
+            MainState::Process(state) => &mut state.home_state,
+            MainState::TemplateEditor(state) => &mut state.home_state,
+            MainState::Conflict(state) => &mut state.home_state,
+        }
+    }
+
+    /// Go home, if the open form edits an item, which does not exist
+    /// anymore.
+    fn close_missing_process(&mut self) {
+        if let MainState::Process(state) = self {
+            if state.process.form(&state.home_state.profile).is_none() {
+                *self = MainState::Home(state.home_state.clone());
+            }
+        }
+    }
 }
 
 #[derive(Clone, Data, Matcher)]
@@ -138,6 +518,78 @@ impl AppState {
         AppState::Main(MainState::Home(HomeState {
             profile: ProfileState::from(profile),
             nav: Nav::Home,
+            palette: None,
+            confirmation: None,
+            confirm_deletions: true,
+            miscellaneous: MiscellaneousState::default(),
+            history: HistoryState::default(),
+            settings: None,
         }))
     }
+
+    /// Add recorded changes to the "Verlauf" page. Without a profile, there
+    /// is no page to show them.
+    pub fn add_changes(&mut self, changes: impl IntoIterator<Item = Change>) {
+        if let AppState::Main(main) = self {
+            main.home_state_mut().history.changes.extend(changes);
+        }
+    }
+
+    /// Show on the settings page, whether the bundle was exported.
+    pub fn set_export_result(&mut self, result: Result<(), String>) {
+        if let AppState::Main(main) = self {
+            if let Some(settings) = &mut main.home_state_mut().settings {
+                settings.set_export_result(result);
+            }
+        }
+    }
+
+    /// Show on the start screen, why the bundle was not imported.
+    pub fn set_import_error(&mut self, error: String) {
+        if let AppState::Create(form) = self {
+            form.set_import_error(error);
+        }
+    }
+
+    /// Take the profile, which was changed on disk, e.g. by a sync client.
+    /// Open forms stay open, unless their item was deleted. If the profile
+    /// of the app was changed since `base` was loaded or saved, both changes
+    /// are merged. Conflicting changes are resolved by the user.
+    ///
+    /// Returns the merged profile, if it has to be saved.
+    pub fn reload_profile(&mut self, base: Option<&Profile>, remote: Profile) -> Option<Profile> {
+        let main = match self {
+            AppState::Create(_) => {
+                *self = AppState::from_profile(remote);
+                return None;
+            }
+            AppState::Main(main) => main,
+        };
+
+        if let MainState::Conflict(state) = main {
+            *state = ConflictState::new(state.home_state.clone(), state.base.get_profile(), remote);
+            return None;
+        }
+
+        let home_state = main.home_state_mut();
+        let base = base
+            .cloned()
+            .unwrap_or_else(|| Profile::new(String::new(), String::new()));
+        if ProfileState::from(base.clone()) == home_state.profile {
+            home_state.profile = ProfileState::from(remote);
+            main.close_missing_process();
+            return None;
+        }
+
+        let state = ConflictState::new(home_state.clone(), base, remote);
+        if state.fields.is_empty() {
+            home_state.profile = state.merged;
+            let profile = home_state.profile.get_profile();
+            main.close_missing_process();
+            return Some(profile);
+        }
+
+        *main = MainState::Conflict(state);
+        None
+    }
 }
@@ -0,0 +1,155 @@
+//! Color tokens of the app. Widgets get their colors from these keys in the
+//! `Env`, never as constants, so the theme can be switched at runtime by
+//! `widgets::ThemeScope`.
+//!
+//! Each palette also sets the colors of the builtin druid widgets, e.g. the
+//! text boxes and scroll bars.
+
+use crate::data::Theme;
+use druid::{theme, ArcStr, Color, Env, Key};
+
+/// Background of the pages, dialogs and popups.
+pub const BACKGROUND: Key<Color> = Key::new("app.theme.background");
+/// Background of the sidebar and of selected rows.
+pub const SURFACE: Key<Color> = Key::new("app.theme.surface");
+pub const BORDER: Key<Color> = Key::new("app.theme.border");
+pub const TEXT: Key<Color> = Key::new("app.theme.text");
+/// Hints, placeholders and drag handles.
+pub const MUTED_TEXT: Key<Color> = Key::new("app.theme.muted_text");
+/// Primary buttons, focus rings and drop indicators.
+pub const ACCENT: Key<Color> = Key::new("app.theme.accent");
+/// Background of selected chips.
+pub const SELECTED: Key<Color> = Key::new("app.theme.selected");
+pub const SUCCESS: Key<Color> = Key::new("app.theme.success");
+pub const WARNING: Key<Color> = Key::new("app.theme.warning");
+pub const DANGER: Key<Color> = Key::new("app.theme.danger");
+/// Text on a success, warning or danger background, e.g. of badges.
+pub const ON_STATUS: Key<Color> = Key::new("app.theme.on_status");
+/// Dims the page behind dialogs.
+pub const OVERLAY: Key<Color> = Key::new("app.theme.overlay");
+
+/// Id of the selected theme, which may be `system`.
+pub const THEME: Key<ArcStr> = Key::new("app.theme");
+
+pub struct Palette {
+    pub background: Color,
+    pub surface: Color,
+    pub border: Color,
+    pub text: Color,
+    pub muted_text: Color,
+    pub accent: Color,
+    pub selected: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub on_status: Color,
+    pub overlay: Color,
+}
+
+pub const DARK: Palette = Palette {
+    background: Color::rgb8(0x31, 0x31, 0x31),
+    surface: Color::rgb8(0x3A, 0x3A, 0x3A),
+    border: Color::rgb8(0xA1, 0xA1, 0xA1),
+    text: Color::rgb8(0xF0, 0xF0, 0xEA),
+    muted_text: Color::rgb8(0xA0, 0xA0, 0x9A),
+    accent: Color::rgb8(0x5C, 0xC4, 0xFF),
+    selected: Color::rgb8(0x00, 0x8D, 0xDD),
+    success: Color::rgb8(0x6A, 0xB0, 0x4C),
+    warning: Color::rgb8(0xE5, 0xA5, 0x3A),
+    danger: Color::rgb8(0xE0, 0x5A, 0x4F),
+    on_status: Color::rgb8(0x31, 0x31, 0x31),
+    overlay: Color::rgba8(0x00, 0x00, 0x00, 0x80),
+};
+
+pub const LIGHT: Palette = Palette {
+    background: Color::rgb8(0xFA, 0xFA, 0xF8),
+    surface: Color::rgb8(0xEC, 0xEC, 0xE8),
+    border: Color::rgb8(0xB4, 0xB4, 0xB0),
+    text: Color::rgb8(0x24, 0x24, 0x22),
+    muted_text: Color::rgb8(0x6E, 0x6E, 0x6A),
+    accent: Color::rgb8(0x00, 0x6F, 0xB8),
+    selected: Color::rgb8(0xC4, 0xE2, 0xF7),
+    success: Color::rgb8(0x4E, 0x94, 0x32),
+    warning: Color::rgb8(0xE0, 0x9A, 0x24),
+    danger: Color::rgb8(0xC8, 0x3E, 0x34),
+    on_status: Color::rgb8(0xFF, 0xFF, 0xFF),
+    overlay: Color::rgba8(0x00, 0x00, 0x00, 0x40),
+};
+
+/// Black and white with bright status colors, for the best readability.
+pub const HIGH_CONTRAST: Palette = Palette {
+    background: Color::BLACK,
+    surface: Color::rgb8(0x1A, 0x1A, 0x1A),
+    border: Color::WHITE,
+    text: Color::WHITE,
+    muted_text: Color::rgb8(0xD8, 0xD8, 0xD8),
+    accent: Color::rgb8(0xFF, 0xE6, 0x00),
+    selected: Color::rgb8(0x00, 0x4C, 0xA8),
+    success: Color::rgb8(0x5C, 0xF0, 0x5C),
+    warning: Color::rgb8(0xFF, 0xE6, 0x00),
+    danger: Color::rgb8(0xFF, 0x6E, 0x6E),
+    on_status: Color::BLACK,
+    overlay: Color::rgba8(0x00, 0x00, 0x00, 0xC0),
+};
+
+impl Palette {
+    /// The palette of a theme. `Theme::System` has to be resolved first.
+    pub fn of(theme: Theme) -> &'static Palette {
+        match theme {
+            Theme::System | Theme::Dark => &DARK,
+            Theme::Light => &LIGHT,
+            Theme::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+
+    pub fn apply(&self, env: &mut Env) {
+        env.set(BACKGROUND, self.background.clone());
+        env.set(SURFACE, self.surface.clone());
+        env.set(BORDER, self.border.clone());
+        env.set(TEXT, self.text.clone());
+        env.set(MUTED_TEXT, self.muted_text.clone());
+        env.set(ACCENT, self.accent.clone());
+        env.set(SELECTED, self.selected.clone());
+        env.set(SUCCESS, self.success.clone());
+        env.set(WARNING, self.warning.clone());
+        env.set(DANGER, self.danger.clone());
+        env.set(ON_STATUS, self.on_status.clone());
+        env.set(OVERLAY, self.overlay.clone());
+
+        env.set(theme::WINDOW_BACKGROUND_COLOR, self.background.clone());
+        env.set(theme::BACKGROUND_DARK, self.background.clone());
+        env.set(theme::BACKGROUND_LIGHT, self.surface.clone());
+        env.set(theme::TEXT_COLOR, self.text.clone());
+        env.set(theme::DISABLED_TEXT_COLOR, self.muted_text.clone());
+        env.set(theme::PLACEHOLDER_COLOR, self.muted_text.clone());
+        env.set(theme::CURSOR_COLOR, self.text.clone());
+        env.set(theme::FOREGROUND_LIGHT, self.text.clone());
+        env.set(theme::FOREGROUND_DARK, self.muted_text.clone());
+        env.set(theme::BUTTON_LIGHT, self.surface.clone());
+        env.set(theme::BUTTON_DARK, self.background.clone());
+        env.set(theme::BORDER_LIGHT, self.border.clone());
+        env.set(theme::BORDER_DARK, self.surface.clone());
+        env.set(theme::PRIMARY_LIGHT, self.accent.clone());
+        env.set(theme::PRIMARY_DARK, self.selected.clone());
+        env.set(theme::SELECTED_TEXT_BACKGROUND_COLOR, self.selected.clone());
+        env.set(theme::SCROLLBAR_COLOR, self.text.clone());
+        env.set(theme::SCROLLBAR_BORDER_COLOR, self.border.clone());
+    }
+}
+
+/// Resolve `Theme::System` to the dark or light theme of the operating
+/// system. The dark theme is used, if the system has no preference.
+pub fn resolve(theme: Theme) -> Theme {
+    match theme {
+        Theme::System => match dark_light::detect() {
+            dark_light::Mode::Light => Theme::Light,
+            _ => Theme::Dark,
+        },
+        theme => theme,
+    }
+}
+
+/// The selected theme, e.g. to show it on the settings page.
+pub fn current(env: &Env) -> Theme {
+    Theme::from_id(&env.get(THEME)).unwrap_or(Theme::System)
+}
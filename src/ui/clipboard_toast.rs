@@ -0,0 +1,137 @@
+//! Copies values to the clipboard and shows a toast with the time until
+//! the clipboard is cleared again.
+
+use crate::clipboard::{ClipboardManager, SystemClipboard};
+use crate::i18n;
+use crate::theme;
+use druid::widget::prelude::*;
+use druid::{Affine, Key, Point, Selector, TextLayout, TimerToken, WidgetPod};
+use std::time::{Duration, Instant};
+
+/// Copy the value to the clipboard.
+pub const COPY: Selector<String> = Selector::new("app.copy");
+
+/// Seconds after which copied values are removed from the clipboard. A
+/// value of zero keeps them on the clipboard.
+pub const CLIPBOARD_TIMEOUT_SECONDS: Key<u64> = Key::new("app.clipboard_timeout_seconds");
+
+/// How long the toast is shown, if the clipboard is not cleared.
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+const TOAST_MARGIN: f64 = 20.0;
+
+pub struct ClipboardToast<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    manager: ClipboardManager<SystemClipboard>,
+    text: TextLayout<String>,
+    is_visible: bool,
+    hide_at: Option<Instant>,
+    timer: TimerToken,
+}
+
+impl<T: Data> ClipboardToast<T> {
+    pub fn new(child: impl Widget<T> + 'static) -> ClipboardToast<T> {
+        ClipboardToast {
+            child: WidgetPod::new(Box::new(child)),
+            manager: ClipboardManager::new(SystemClipboard),
+            text: TextLayout::new(),
+            is_visible: false,
+            hide_at: None,
+            timer: TimerToken::INVALID,
+        }
+    }
+
+    fn toast_text(&self, now: Instant) -> String {
+        match self.manager.remaining(now) {
+            Some(remaining) => i18n::text_with(
+                "clipboard-copied-until",
+                &[("seconds", (remaining.as_secs_f64().ceil() as u64).into())],
+            ),
+            None => i18n::text("clipboard-copied"),
+        }
+    }
+
+    /// Update the toast every second, while it is visible.
+    fn refresh(&mut self, ctx: &mut EventCtx, now: Instant) {
+        let is_pending = self.manager.tick(now);
+        let is_visible = self.hide_at.map_or(false, |hide_at| now < hide_at);
+
+        self.is_visible = is_pending || is_visible;
+        if self.is_visible {
+            self.text.set_text(self.toast_text(now));
+            self.timer = ctx.request_timer(Duration::from_secs(1));
+        } else {
+            self.hide_at = None;
+        }
+        ctx.request_layout();
+    }
+}
+
+impl<T: Data> Widget<T> for ClipboardToast<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(COPY) => {
+                let now = Instant::now();
+                let timeout = match env.get(CLIPBOARD_TIMEOUT_SECONDS) {
+                    0 => None,
+                    seconds => Some(Duration::from_secs(seconds)),
+                };
+
+                self.manager.copy(cmd.get_unchecked(COPY), timeout, now);
+                self.hide_at = Some(now + TOAST_DURATION);
+                self.refresh(ctx, now);
+                ctx.set_handled();
+            }
+            Event::Timer(token) if *token == self.timer => {
+                self.refresh(ctx, Instant::now());
+                ctx.set_handled();
+            }
+            _ => self.child.event(ctx, event, data, env),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.text.set_text_color(theme::BACKGROUND);
+        }
+        self.child.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        // The text color changes with the theme.
+        if self.text.needs_rebuild_after_update(ctx) {
+            ctx.request_layout();
+        }
+        self.child.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, data, env, Point::ORIGIN);
+
+        self.text.rebuild_if_needed(ctx.text(), env);
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+
+        if !self.is_visible {
+            return;
+        }
+
+        let size = ctx.size();
+        let padding = Size::new(16.0, 8.0);
+        let toast_size = self.text.size() + padding * 2.0;
+        let origin = Point::new(
+            (size.width - toast_size.width) / 2.0,
+            size.height - toast_size.height - TOAST_MARGIN,
+        );
+
+        ctx.with_save(|ctx| {
+            ctx.transform(Affine::translate(origin.to_vec2()));
+            ctx.fill(toast_size.to_rounded_rect(4.0), &env.get(theme::TEXT));
+            self.text.draw(ctx, (padding.width, padding.height));
+        });
+    }
+}
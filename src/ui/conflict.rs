@@ -0,0 +1,85 @@
+//! Shown, when the profile was changed on disk, while it also had changes in
+//! the app, e.g. on two machines, which sync the data directory.
+//!
+//! Like the forms, the screen only emits the `RESOLVED` notification and the
+//! surrounding controller saves the resolved profile.
+
+use super::form::centered;
+use crate::i18n;
+use crate::state::{ConflictState, FieldChoice, Resolution};
+use crate::widgets::{Card, OutlineButton, SecretLabel};
+use druid::widget::{CrossAxisAlignment, Flex, Label, LineBreaking, List, Radio};
+use druid::{Selector, Widget, WidgetExt};
+
+/// The user chose how to resolve the conflict.
+pub const RESOLVED: Selector<Resolution> = Selector::new("app.main.conflict.resolved");
+
+pub fn build() -> impl Widget<ConflictState> {
+    centered(Card::new(
+        Flex::column()
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .with_child(Label::new(i18n::text("conflict-title")).with_text_size(20.0))
+            .with_default_spacer()
+            .with_child(
+                Label::new(i18n::text("conflict-description"))
+                    .with_line_break_mode(LineBreaking::WordWrap),
+            )
+            .with_spacer(20.0)
+            .with_child(
+                List::new(build_field)
+                    .with_spacing(20.0)
+                    .lens(ConflictState::fields),
+            )
+            .with_spacer(20.0)
+            .with_child(
+                Flex::row()
+                    .with_child(resolve_button("conflict-keep-mine", Resolution::KeepMine))
+                    .with_default_spacer()
+                    .with_child(resolve_button(
+                        "conflict-take-theirs",
+                        Resolution::TakeTheirs,
+                    ))
+                    .with_default_spacer()
+                    .with_child(resolve_button("conflict-merge", Resolution::Merge).primary()),
+            ),
+    ))
+}
+
+fn resolve_button(title: &str, resolution: Resolution) -> OutlineButton<ConflictState> {
+    OutlineButton::new(i18n::text(title))
+        .on_click(move |ctx, _, _| ctx.submit_notification(RESOLVED.with(resolution)))
+}
+
+/// Both values of a field, the selected one is taken when merging.
+fn build_field() -> impl Widget<FieldChoice> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            Label::dynamic(|choice: &FieldChoice, _env| choice.label.clone()).with_text_size(16.0),
+        )
+        .with_default_spacer()
+        .with_child(build_value("conflict-mine", false, |choice| {
+            choice.local.clone()
+        }))
+        .with_default_spacer()
+        .with_child(build_value("conflict-theirs", true, |choice| {
+            choice.remote.clone()
+        }))
+}
+
+fn build_value(
+    title: &str,
+    take_remote: bool,
+    value: impl Fn(&FieldChoice) -> String + 'static,
+) -> impl Widget<FieldChoice> {
+    Flex::row()
+        .with_child(Radio::new(i18n::text(title), take_remote).lens(FieldChoice::take_remote))
+        .with_default_spacer()
+        .with_flex_child(
+            SecretLabel::new(
+                move |choice: &FieldChoice, _env| value(choice),
+                |choice: &FieldChoice, _env| choice.is_secret,
+            ),
+            1.0,
+        )
+}
@@ -0,0 +1,200 @@
+//! Processes to add or edit data of the profile. Each process is described
+//! by the fields of its form.
+
+use super::form::{Field, Form};
+use crate::data::{
+    document_type, format_tags, parse_tags, BankAccount, DocumentType, Entry, EntryValue,
+    FieldKind, ItemId, KeyValueItem, Template, TemplateFieldKind, CATEGORIES,
+};
+use crate::i18n;
+use crate::state::ProfileState;
+use crate::widgets::parse_date;
+use druid::Data;
+
+#[derive(Clone, Copy, Data, PartialEq, Eq, Debug)]
+pub enum Process {
+    /// Create a document of the registered type with the given id.
+    CreateDocument(&'static str),
+    CreateBankAccount,
+    CreateKeyValueItem,
+    EditKeyValueItem(ItemId),
+    /// Create an entry from the template with the given id.
+    CreateEntry(ItemId),
+}
+
+impl Process {
+    /// The form of the process, `None` if the edited item or the template
+    /// does not exist anymore, e.g. after the profile was reloaded.
+    pub fn form(self, profile: &ProfileState) -> Option<Form> {
+        let form = match self {
+            Process::CreateDocument(id) => {
+                document_form(document_type(id).expect("Document type is not registered"))
+            }
+            Process::CreateBankAccount => {
+                Form::new(i18n::text("bank-account-create"), |state, profile| {
+                    profile.bank_accounts.push_back(BankAccount {
+                        id: ItemId::new(),
+                        name: state.value(0).into(),
+                        iban: state.value(1).into(),
+                        pinned: false,
+                    });
+                    Ok(())
+                })
+                .field(Field::text(i18n::text("field-name")))
+                .field(Field::text(i18n::text("field-iban")))
+            }
+            Process::CreateKeyValueItem => {
+                Form::new(i18n::text("item-create"), |state, profile| {
+                    profile.key_value_items.push_back(KeyValueItem {
+                        id: ItemId::new(),
+                        key: state.value(0).into(),
+                        value: state.value(1).into(),
+                        secret: false,
+                        pinned: false,
+                        category: state.value(2).trim().into(),
+                        tags: parse_tags(state.value(3)),
+                    });
+                    Ok(())
+                })
+                .field(Field::text(i18n::text("field-name")))
+                .field(Field::text(i18n::text("field-value")))
+                .field(category_field())
+                .field(Field::text(i18n::text("field-tags")).optional())
+            }
+            Process::EditKeyValueItem(id) => {
+                let item = profile
+                    .key_value_items
+                    .iter()
+                    .find(|item| item.id == id)?
+                    .clone();
+
+                Form::new(i18n::text("item-edit"), move |state, profile| {
+                    for item in profile.key_value_items.iter_mut() {
+                        if item.id == id {
+                            item.key = state.value(0).into();
+                            item.value = state.value(1).into();
+                            item.category = state.value(2).trim().into();
+                            item.tags = parse_tags(state.value(3));
+                        }
+                    }
+                    Ok(())
+                })
+                .submit_label(i18n::text("save"))
+                .field(Field::text(i18n::text("field-name")).value(item.key.as_str()))
+                .field(Field::text(i18n::text("field-value")).value(item.value.as_str()))
+                .field(category_field().value(item.category.as_str()))
+                .field(
+                    Field::text(i18n::text("field-tags"))
+                        .optional()
+                        .value(format_tags(&item.tags)),
+                )
+            }
+            Process::CreateEntry(template_id) => entry_form(
+                profile
+                    .templates
+                    .iter()
+                    .find(|template| template.id == template_id)?
+                    .clone(),
+            ),
+        };
+
+        Some(form)
+    }
+}
+
+fn category_field() -> Field {
+    Field::text(i18n::text_with(
+        "field-category",
+        &[("examples", CATEGORIES.join(", ").into())],
+    ))
+    .optional()
+}
+
+fn document_form(document_type: &'static dyn DocumentType) -> Form {
+    let fields = document_type.fields();
+
+    let form = Form::new(
+        i18n::text_with(
+            "document-create",
+            &[("name", i18n::text(document_type.name()).into())],
+        ),
+        move |state, profile| {
+            let values: Vec<String> = fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| match field.kind {
+                    FieldKind::Text => String::from(state.value(index)),
+                    // Dates are stored as `2030-02-01`, empty optional dates stay empty.
+                    FieldKind::Date => parse_date(state.value(index))
+                        .map(|date| date.to_string())
+                        .unwrap_or_default(),
+                })
+                .collect();
+
+            let document = document_type
+                .parse(&values)
+                .map_err(|error| i18n::error(&error))?;
+            profile.set_document(document_type.id(), Some(document));
+            Ok(())
+        },
+    );
+
+    document_type
+        .fields()
+        .into_iter()
+        .fold(form, |form, spec| form.field(Field::from_spec(spec)))
+}
+
+/// The entry gets a name and one optional field per template field.
+fn entry_form(template: Template) -> Form {
+    let fields: Vec<Field> = template
+        .fields
+        .iter()
+        .map(|field| match field.kind {
+            TemplateFieldKind::Date => Field::date(field.label.as_str()).optional(),
+            kind => Field::text(field.label.as_str())
+                .optional()
+                .parse(move |value| kind.parse(value).map_err(|error| i18n::error(&error))),
+        })
+        .collect();
+
+    let form = Form::new(
+        i18n::text_with("entry-create", &[("name", template.name.as_str().into())]),
+        move |state, profile| {
+            let values = template
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| {
+                    let value = match field.kind {
+                        TemplateFieldKind::Date => parse_date(state.value(index + 1))
+                            .map(|date| date.to_string())
+                            .unwrap_or_default(),
+                        kind => match state.value(index + 1).trim() {
+                            "" => String::new(),
+                            value => kind.parse(value).map_err(|error| i18n::error(&error))?,
+                        },
+                    };
+
+                    Ok(EntryValue {
+                        field_id: field.id,
+                        label: field.label.clone(),
+                        kind: field.kind,
+                        value,
+                    })
+                })
+                .collect::<Result<Vec<EntryValue>, String>>()?;
+
+            profile.entries.push_back(Entry {
+                id: ItemId::new(),
+                template_id: template.id,
+                name: String::from(state.value(0).trim()),
+                values,
+            });
+            Ok(())
+        },
+    )
+    .field(Field::text(i18n::text("field-name")));
+
+    fields.into_iter().fold(form, Form::field)
+}
@@ -1,22 +1,88 @@
+use super::form::{centered, FormShortcuts, SUBMITTED};
 use crate::data::Profile;
-use crate::widgets::{input, Card, InputState, OutlineButton};
-use druid::widget::{CrossAxisAlignment, Flex, Label, MainAxisAlignment, Widget, WidgetExt};
-use druid::{Data, Lens, Selector};
+use crate::i18n;
+use crate::theme;
+use crate::widgets::{input, Card, InputState, OutlineButton, AUTOFOCUS};
+use druid::widget::{
+    Controller, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Widget, WidgetExt,
+};
+use druid::{Data, Env, Event, EventCtx, Lens, Selector};
 
 pub const PROFILE_CREATED: Selector<Profile> = Selector::new("app.main.profile_created");
+/// Choose a bundle and import it with the given passphrase.
+pub const IMPORT_BUNDLE: Selector<String> = Selector::new("app.import_bundle");
 
 #[derive(Data, Lens, PartialEq, Eq, Clone, Default, Debug)]
 pub struct FormState {
     first_name: InputState,
     last_name: InputState,
+    /// Passphrase of the bundle to import.
+    passphrase: InputState,
+}
+
+impl FormState {
+    pub fn set_import_error(&mut self, error: String) {
+        self.passphrase.set_error(error);
+    }
 }
 
 pub fn build() -> impl Widget<FormState> {
-    Flex::column()
-        .must_fill_main_axis(true)
-        .cross_axis_alignment(CrossAxisAlignment::Center)
-        .main_axis_alignment(MainAxisAlignment::Center)
-        .with_child(form())
+    centered(form()).controller(FormController)
+}
+
+/// Creates the profile, once the form is submitted.
+struct FormController;
+
+impl<W> Controller<FormState, W> for FormController
+where
+    W: Widget<FormState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut FormState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Notification(not) if not.is(SUBMITTED) => {
+                submit(ctx, data);
+                ctx.set_handled();
+            }
+            _ => {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+}
+
+fn submit(ctx: &mut EventCtx, state: &mut FormState) {
+    let mut has_error = false;
+
+    state.first_name.reset_error();
+    if state.first_name.value.is_empty() {
+        state
+            .first_name
+            .set_error(i18n::text("profile-first-name-required"));
+        has_error = true;
+    }
+
+    state.last_name.reset_error();
+    if state.last_name.value.is_empty() {
+        state
+            .last_name
+            .set_error(i18n::text("profile-last-name-required"));
+        has_error = true;
+    }
+
+    if !has_error {
+        let first_name = state.first_name.value.as_str();
+        let last_name = state.last_name.value.as_str();
+        let profile = Profile::new(first_name.into(), last_name.into());
+
+        ctx.submit_notification(PROFILE_CREATED.with(profile));
+    }
 }
 
 fn form() -> impl Widget<FormState> {
@@ -25,41 +91,56 @@ fn form() -> impl Widget<FormState> {
             .must_fill_main_axis(true)
             .cross_axis_alignment(CrossAxisAlignment::Center)
             .main_axis_alignment(MainAxisAlignment::Center)
-            .with_child(Label::new("Profil erstellen").with_text_size(20.0))
-            .with_spacer(20.0)
-            .with_child(input("Vorname").lens(FormState::first_name))
-            .with_default_spacer()
-            .with_child(input("Nachname").lens(FormState::last_name))
-            .with_spacer(20.0)
-            .with_child(OutlineButton::new("Erstellen").on_click(
-                |ctx, state: &mut FormState, _env| {
-                    let mut has_error = false;
-
-                    state.first_name.reset_error();
-                    if state.first_name.value.is_empty() {
-                        state
-                            .first_name
-                            .set_error(String::from("Fist name must not be empty"));
-                        has_error = true;
-                    }
-
-                    state.last_name.reset_error();
-                    if state.last_name.value.is_empty() {
-                        state
-                            .last_name
-                            .set_error(String::from("Last name must not be empty"));
-                        has_error = true;
-                    }
-
-                    if !has_error {
-                        let first_name = state.first_name.value.as_str();
-                        let last_name = state.last_name.value.as_str();
-                        let profile = Profile::new(first_name.into(), last_name.into());
-
-                        ctx.submit_notification(PROFILE_CREATED.with(profile));
-                    }
-                },
-            )),
+            .with_child(create_form())
+            .with_spacer(40.0)
+            .with_child(import_form()),
     )
     .fix_width(400.0)
 }
+
+fn create_form() -> impl Widget<FormState> {
+    Flex::column()
+        .with_child(Label::new(i18n::text("profile-create-title")).with_text_size(20.0))
+        .with_spacer(20.0)
+        .with_child(
+            input(&i18n::text("profile-first-name"))
+                .env_scope(|env, _| env.set(AUTOFOCUS, true))
+                .lens(FormState::first_name),
+        )
+        .with_default_spacer()
+        .with_child(input(&i18n::text("profile-last-name")).lens(FormState::last_name))
+        .with_spacer(20.0)
+        .with_child(
+            OutlineButton::new(i18n::text("create"))
+                .primary()
+                .disabled_if(|state: &FormState, _env| {
+                    state.first_name.value.is_empty() || state.last_name.value.is_empty()
+                })
+                .on_click(|ctx, _state, _env| ctx.submit_notification(SUBMITTED)),
+        )
+        .controller(FormShortcuts)
+}
+
+/// Restores the bundle, which was exported on another machine. The file is
+/// chosen after the passphrase was entered.
+fn import_form() -> impl Widget<FormState> {
+    Flex::column()
+        .with_child(Label::new(i18n::text("profile-import-title")).with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(
+            Label::new(i18n::text("profile-import-hint"))
+                .with_text_color(theme::MUTED_TEXT)
+                .with_text_size(12.0),
+        )
+        .with_default_spacer()
+        .with_child(input(&i18n::text("bundle-passphrase")).lens(FormState::passphrase))
+        .with_default_spacer()
+        .with_child(
+            OutlineButton::new(i18n::text("profile-import"))
+                .disabled_if(|state: &FormState, _env| state.passphrase.value.is_empty())
+                .on_click(|ctx, state: &mut FormState, _env| {
+                    state.passphrase.reset_error();
+                    ctx.submit_command(IMPORT_BUNDLE.with(state.passphrase.value.to_string()))
+                }),
+        )
+}
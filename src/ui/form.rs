@@ -0,0 +1,300 @@
+//! Declarative forms, which are described by a list of fields and a
+//! function to add the entered values to the profile.
+//!
+//! A form only emits the `SUBMITTED` and `CANCELED` notifications, the
+//! validation and the update of the profile is done by the surrounding
+//! controller by calling `Form::submit`.
+//!
+//! The first field is focused when the form is shown, Enter submits the
+//! form and Escape cancels it.
+
+use crate::data::{FieldKind, FieldSpec};
+use crate::i18n;
+use crate::state::ProfileState;
+use crate::theme;
+use crate::widgets::{date_input, input, parse_date, Card, InputState, OutlineButton, AUTOFOCUS};
+use druid::im::Vector;
+use druid::widget::{
+    Controller, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Maybe, Scroll, Widget,
+    WidgetExt,
+};
+use druid::{Data, Env, Event, EventCtx, KbKey, Lens, LensExt, Selector};
+use std::fmt::Display;
+use std::sync::Arc;
+
+pub const SUBMITTED: Selector<()> = Selector::new("app.main.form.submitted");
+pub const CANCELED: Selector<()> = Selector::new("app.main.form.canceled");
+
+/// Emits `SUBMITTED` for Enter and `CANCELED` for Escape, if the focused
+/// widget, e.g. a button or the calendar, does not handle the key itself.
+pub struct FormShortcuts;
+
+impl<T, W: Widget<T>> Controller<T, W> for FormShortcuts {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        child.event(ctx, event, data, env);
+
+        if let Event::KeyDown(key) = event {
+            if ctx.is_handled() || !key.mods.is_empty() {
+                return;
+            }
+
+            match &key.key {
+                KbKey::Enter => {
+                    ctx.submit_notification(SUBMITTED);
+                    ctx.set_handled();
+                }
+                KbKey::Escape => {
+                    ctx.submit_notification(CANCELED);
+                    ctx.set_handled();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Centers the card of a form on the page. The card scrolls, if it is
+/// higher than the window.
+pub fn centered<T: Data>(card: impl Widget<T> + 'static) -> impl Widget<T> {
+    Scroll::new(card.padding(20.0)).vertical().center()
+}
+
+type Parser = Box<dyn Fn(&str) -> Result<(), String>>;
+type Apply = Box<dyn Fn(&FormState, &mut ProfileState) -> Result<(), String>>;
+
+pub struct Field {
+    label: String,
+    kind: FieldKind,
+    required: bool,
+    parser: Option<Parser>,
+    /// The initial value, e.g. of an edited item.
+    value: String,
+}
+
+impl Field {
+    /// A required text field.
+    pub fn text(label: impl Into<String>) -> Field {
+        Field {
+            label: label.into(),
+            kind: FieldKind::Text,
+            required: true,
+            parser: None,
+            value: String::new(),
+        }
+    }
+
+    /// A required date field, which accepts the formats of `parse_date`.
+    pub fn date(label: impl Into<String>) -> Field {
+        Field {
+            label: label.into(),
+            kind: FieldKind::Date,
+            required: true,
+            parser: None,
+            value: String::new(),
+        }
+        .parse(parse_date)
+    }
+
+    pub fn optional(mut self) -> Field {
+        self.required = false;
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<String>) -> Field {
+        self.value = value.into();
+        self
+    }
+
+    /// Validate non-empty values with `parser`, e.g. `TaxId::try_from`.
+    pub fn parse<T, E: Display>(
+        mut self,
+        parser: impl Fn(&str) -> Result<T, E> + 'static,
+    ) -> Field {
+        self.parser = Some(Box::new(move |value| {
+            parser(value).map(|_| ()).map_err(|error| error.to_string())
+        }));
+        self
+    }
+
+    /// Create a field from the field of a document type.
+    pub fn from_spec(spec: FieldSpec) -> Field {
+        let field = match spec.kind {
+            FieldKind::Text => Field::text(i18n::text(spec.label)),
+            FieldKind::Date => Field::date(i18n::text(spec.label)),
+        };
+        let field = match spec.required {
+            true => field,
+            false => field.optional(),
+        };
+
+        match (spec.kind, spec.validate) {
+            (FieldKind::Text, Some(validate)) => {
+                field.parse(move |value| validate(value).map_err(|error| i18n::error(&error)))
+            }
+            _ => field,
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<(), String> {
+        if value.trim().is_empty() {
+            return match self.required {
+                true => Err(i18n::text("error-required")),
+                false => Ok(()),
+            };
+        }
+
+        match &self.parser {
+            Some(parser) => parser(value),
+            None => Ok(()),
+        }
+    }
+
+    fn build(&self) -> Box<dyn Widget<InputState>> {
+        match self.kind {
+            FieldKind::Text => Box::new(input(&self.label)),
+            FieldKind::Date => Box::new(date_input(&self.label)),
+        }
+    }
+}
+
+pub struct Form {
+    title: String,
+    submit_label: String,
+    fields: Vec<Field>,
+    apply: Apply,
+}
+
+impl Form {
+    /// Create a form, which calls `apply` with the validated values when
+    /// it is submitted.
+    pub fn new(
+        title: impl Into<String>,
+        apply: impl Fn(&FormState, &mut ProfileState) -> Result<(), String> + 'static,
+    ) -> Form {
+        Form {
+            title: title.into(),
+            submit_label: i18n::text("create"),
+            fields: Vec::new(),
+            apply: Box::new(apply),
+        }
+    }
+
+    pub fn field(mut self, field: Field) -> Form {
+        self.fields.push(field);
+        self
+    }
+
+    /// Label of the submit button, "Erstellen" or "Create" by default.
+    pub fn submit_label(mut self, label: impl Into<String>) -> Form {
+        self.submit_label = label.into();
+        self
+    }
+
+    pub fn build(&self) -> impl Widget<FormState> {
+        let mut column = Flex::column()
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .main_axis_alignment(MainAxisAlignment::Start)
+            .with_child(Label::new(self.title.as_str()).with_text_size(20.0))
+            .with_spacer(20.0);
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if index > 0 {
+                column.add_default_spacer();
+            }
+            let field = field
+                .build()
+                .env_scope(move |env, _| env.set(AUTOFOCUS, index == 0));
+            column.add_child(field.lens(FormState::fields.index(index)));
+        }
+
+        column.add_child(
+            Maybe::or_empty(|| {
+                Label::dynamic(|error: &String, _| error.to_string())
+                    .with_text_color(theme::MUTED_TEXT)
+                    .with_text_size(12.0)
+                    .padding((0.0, 5.0, 0.0, 0.0))
+            })
+            .lens(FormState::error),
+        );
+        // Submitting is disabled, until all required fields are filled.
+        let required: Vec<usize> = (0..self.fields.len())
+            .filter(|index| self.fields[*index].required)
+            .collect();
+
+        column.add_spacer(20.0);
+        column.add_child(
+            Flex::row()
+                .with_child(
+                    OutlineButton::new(self.submit_label.as_str())
+                        .primary()
+                        .disabled_if(move |state: &FormState, _env| {
+                            required
+                                .iter()
+                                .any(|index| state.value(*index).trim().is_empty())
+                        })
+                        .on_click(|ctx, _state, _env| ctx.submit_notification(SUBMITTED)),
+                )
+                .with_default_spacer()
+                .with_child(
+                    OutlineButton::new(i18n::text("cancel"))
+                        .on_click(|ctx, _state, _env| ctx.submit_notification(CANCELED)),
+                ),
+        );
+
+        centered(Card::new(column.controller(FormShortcuts)).fix_width(400.0))
+    }
+
+    /// Validate all fields and, if they are valid, add the values to
+    /// `profile`. Errors are shown in the form and `false` is returned.
+    pub fn submit(&self, state: &mut FormState, profile: &mut ProfileState) -> bool {
+        let mut has_error = false;
+
+        for (field, input) in self.fields.iter().zip(state.fields.iter_mut()) {
+            input.reset_error();
+            if let Err(error) = field.validate(input.value.as_str()) {
+                input.set_error(error);
+                has_error = true;
+            }
+        }
+
+        state.error = None;
+        if has_error {
+            return false;
+        }
+
+        match (self.apply)(state, profile) {
+            Ok(()) => true,
+            Err(error) => {
+                state.error = Some(error);
+                false
+            }
+        }
+    }
+}
+
+#[derive(Data, Lens, PartialEq, Eq, Clone, Default, Debug)]
+pub struct FormState {
+    fields: Vector<InputState>,
+    error: Option<String>,
+}
+
+impl FormState {
+    pub fn new(form: &Form) -> FormState {
+        FormState {
+            fields: form
+                .fields
+                .iter()
+                .map(|field| InputState {
+                    value: Arc::new(field.value.clone()),
+                    error: None,
+                })
+                .collect(),
+            error: None,
+        }
+    }
+
+    /// The entered value of the field at `index`.
+    pub fn value(&self, index: usize) -> &str {
+        self.fields[index].value.as_str()
+    }
+}
@@ -0,0 +1,232 @@
+//! The "Verlauf" page lists the recorded changes of the profile, the newest
+//! first, and restores the value of a field from before a change.
+
+use super::main::PROFILE_UPDATED;
+use crate::data::{Change, ProfileField};
+use crate::i18n;
+use crate::state::{field_label, HistoryState, HomeState, ProfileState};
+use crate::theme;
+use crate::widgets::{format_date, OutlineButton, SecretLabel, VirtualList};
+use chrono::Local;
+use druid::im::Vector;
+use druid::lens::Map;
+use druid::widget::{
+    Controller, CrossAxisAlignment, Either, Flex, Label, LineBreaking, List, Painter, SizedBox,
+};
+use druid::{Data, Env, Event, EventCtx, RenderContext, Selector, Widget, WidgetExt};
+use std::iter;
+use std::sync::Arc;
+
+const ROW_HEIGHT: f64 = 80.0;
+
+const FILTER_FIELD: Selector<Option<ProfileField>> = Selector::new("app.history.filter_field");
+/// Restore the old value of the change with the given index.
+const RESTORE: Selector<usize> = Selector::new("app.history.restore");
+
+#[derive(Clone, Data)]
+struct HistoryRow {
+    /// Index of the change in the history.
+    index: usize,
+    change: Change,
+    /// Whether the field has another value than before the change.
+    can_restore: bool,
+}
+
+#[derive(Clone, Data)]
+struct FieldChip {
+    /// `None` shows all changes.
+    field: Option<ProfileField>,
+    label: String,
+    is_selected: bool,
+}
+
+/// Filters the changes by field and restores old values.
+struct HistoryController;
+
+impl<W> Controller<HomeState, W> for HistoryController
+where
+    W: Widget<HomeState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut HomeState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Notification(not) if not.is(FILTER_FIELD) => {
+                data.history.filter = not.get(FILTER_FIELD).unwrap().clone();
+                ctx.set_handled();
+            }
+            Event::Notification(not) if not.is(RESTORE) => {
+                let change = &data.history.changes[*not.get(RESTORE).unwrap()];
+                let mut profile = data.profile.get_profile();
+                profile.set_field_value(&change.field, change.old.as_ref());
+
+                // The restore is recorded as a change of its own.
+                data.profile = ProfileState::from(profile.clone());
+                ctx.submit_notification(PROFILE_UPDATED.with(profile));
+                ctx.set_handled();
+            }
+            _ => {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+}
+
+/// Name of the changed field, with the name of the bank account, template
+/// or entry.
+fn change_label(change: &Change) -> String {
+    match change.item_name() {
+        Some(name) => format!("{}: {}", field_label(&change.field), name),
+        None => field_label(&change.field),
+    }
+}
+
+/// The changes of the selected field, the newest first.
+fn history_rows(state: &HomeState) -> Vector<HistoryRow> {
+    let profile = state.profile.get_profile();
+
+    state
+        .history
+        .changes
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, change)| match &state.history.filter {
+            Some(field) => change.field == *field,
+            None => true,
+        })
+        .map(|(index, change)| HistoryRow {
+            index,
+            can_restore: profile.field_value(&change.field) != change.old,
+            change: change.clone(),
+        })
+        .collect()
+}
+
+/// A chip for all changes and one per changed field, labeled like its
+/// latest change.
+fn field_chips(history: &HistoryState) -> Arc<Vec<FieldChip>> {
+    let mut fields: Vec<(ProfileField, String)> = Vec::new();
+    for change in history.changes.iter().rev() {
+        if !fields.iter().any(|(field, _)| *field == change.field) {
+            fields.push((change.field.clone(), change_label(change)));
+        }
+    }
+
+    let chips = iter::once((None, i18n::text("tags-all")))
+        .chain(
+            fields
+                .into_iter()
+                .map(|(field, label)| (Some(field), label)),
+        )
+        .map(|(field, label)| FieldChip {
+            is_selected: field == history.filter,
+            field,
+            label,
+        })
+        .collect();
+
+    Arc::new(chips)
+}
+
+pub fn build_page() -> impl Widget<HomeState> {
+    let chips = List::new(build_field_chip)
+        .horizontal()
+        .with_spacing(5.0)
+        .lens(Map::new(
+            |state: &HomeState| field_chips(&state.history),
+            |_state: &mut HomeState, _chips: Arc<Vec<FieldChip>>| {},
+        ));
+
+    Either::new(
+        |state: &HomeState, _env| state.history.changes.is_empty(),
+        Label::new(i18n::text("history-empty")).align_left(),
+        Flex::column()
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .with_child(chips)
+            .with_default_spacer()
+            .with_flex_child(
+                VirtualList::new(history_rows, build_change, |_row: &HistoryRow| ROW_HEIGHT),
+                1.0,
+            ),
+    )
+    .padding(10.0)
+    .controller(HistoryController)
+}
+
+fn build_field_chip() -> impl Widget<FieldChip> {
+    Label::dynamic(|chip: &FieldChip, _env| chip.label.clone())
+        .with_text_size(12.0)
+        .padding((8.0, 2.0))
+        .background(Painter::new(|ctx, chip: &FieldChip, env| {
+            let rect = ctx.size().to_rounded_rect(10.0);
+            match chip.is_selected {
+                true => ctx.fill(rect, &env.get(theme::SELECTED)),
+                false => ctx.stroke(rect, &env.get(theme::BORDER), 1.0),
+            }
+        }))
+        .on_click(|ctx, chip: &mut FieldChip, _env| {
+            ctx.submit_notification(FILTER_FIELD.with(chip.field.clone()))
+        })
+}
+
+/// The time and field of a change with the old and the new value.
+fn build_change() -> impl Widget<HistoryRow> {
+    let value = |describe: fn(&Change) -> Option<String>| {
+        SecretLabel::new(
+            move |row: &HistoryRow, _env| {
+                describe(&row.change).unwrap_or_else(|| i18n::text("conflict-empty"))
+            },
+            |row: &HistoryRow, _env| row.change.is_secret(),
+        )
+    };
+
+    Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .must_fill_main_axis(true)
+        .with_flex_child(
+            Flex::column()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(
+                    Label::dynamic(|row: &HistoryRow, env| format_timestamp(&row.change, env))
+                        .with_text_size(12.0)
+                        .with_text_color(theme::MUTED_TEXT),
+                )
+                .with_child(
+                    Label::dynamic(|row: &HistoryRow, _env| change_label(&row.change))
+                        .with_line_break_mode(LineBreaking::Clip),
+                )
+                .with_child(
+                    Flex::row()
+                        .with_child(value(Change::describe_old))
+                        .with_child(Label::new("→").padding((5.0, 0.0)))
+                        .with_child(value(Change::describe_new)),
+                ),
+            1.0,
+        )
+        .with_default_spacer()
+        .with_child(Either::new(
+            |row: &HistoryRow, _env| row.can_restore,
+            OutlineButton::new(i18n::text("history-restore")).on_click(
+                |ctx, row: &mut HistoryRow, _env| ctx.submit_notification(RESTORE.with(row.index)),
+            ),
+            SizedBox::empty(),
+        ))
+        .padding(10.0)
+}
+
+/// Date and time of a change in the local time zone.
+fn format_timestamp(change: &Change, env: &Env) -> String {
+    let timestamp = change.timestamp.with_timezone(&Local);
+
+    format!(
+        "{} {}",
+        format_date(timestamp.naive_local().date(), env),
+        timestamp.format("%H:%M")
+    )
+}
@@ -1,45 +1,65 @@
+use super::conflict;
+use super::create::Process;
+use super::form;
+use super::history;
+use super::palette;
+use super::reorder;
+use super::settings::{self, SettingsState};
 use super::some_lens::SomeLens;
-use crate::data::{BankAccount, IdCard, KeyValueItem, Name, Profile};
+use super::template_editor;
+use super::{COPY, EXPIRY_WARNING_DAYS, EXPORT_CALENDAR};
+use crate::data::{
+    all_tags, document_type, document_types, group_by_category, sort_by_expiry, today, BankAccount,
+    Document, DocumentType, Entry, EntryValue, Expiry, ExpiryStatus, ItemId, KeyValueItem, Name,
+    Profile, Template, TemplateFieldKind, UNCATEGORIZED,
+};
+use crate::i18n;
 use crate::state::{
-    CreateBankAccountState, CreateIdCardState, CreateKeyValueItemState, CreatePostNumberState,
-    CreateSocialSecurityNumberState, CreateTaxIdState, HomeState, MainState, Nav, ProfileState,
+    Confirmation, ConflictState, Deletion, HomeState, MainState, Nav, ProcessState, ProfileState,
+    TemplateEditorState,
+};
+use crate::theme;
+use crate::widgets::{
+    badge, confirm_dialog, date_format, format_date, ConfirmState, OutlineButton, Overlay,
+    SecretLabel, SidebarLayout, VirtualList, CONFIRMED, DISMISSED,
 };
-use crate::ui::create_bank_account;
-use crate::ui::create_id_card;
-use crate::ui::create_key_value_item;
-use crate::ui::create_post_number;
-use crate::ui::create_social_security_number;
-use crate::ui::create_tax_id;
-use crate::widgets::OutlineButton;
+use chrono::{Duration, NaiveDate};
+use druid::im::Vector;
+use druid::lens::Map;
 use druid::widget::{
-    Controller, CrossAxisAlignment, Flex, Label, List, MainAxisAlignment, SizedBox, Split, Svg,
-    SvgData, ViewSwitcher,
+    Controller, CrossAxisAlignment, Either, Flex, Label, LineBreaking, List, MainAxisAlignment,
+    Maybe, Painter, Scroll, SizedBox, Svg, SvgData, ViewSwitcher,
+};
+use druid::{
+    Color, Data, Env, Event, EventCtx, LensExt, RenderContext, Selector, Widget, WidgetExt,
 };
-use druid::{theme, Application, Env, Event, EventCtx, LensExt, Selector, Widget, WidgetExt};
+use druid_enums::Matcher;
+use std::iter;
+use std::str::FromStr;
+use std::sync::Arc;
 
 const START_PROCESS: Selector<Process> = Selector::new("app.start_process");
+const EDIT_TEMPLATE: Selector<Option<ItemId>> = Selector::new("app.edit_template");
 const GO_TO_HOME: Selector<HomeState> = Selector::new("app.main.go_to_home");
-const NAVIGATE: Selector<Nav> = Selector::new("app.navigate");
+pub const NAVIGATE: Selector<Nav> = Selector::new("app.navigate");
+/// Start the create process of the current page.
+pub const CREATE_ON_PAGE: Selector<()> = Selector::new("app.main.create_on_page");
 
-const CLEAR_ID_CARD: Selector<()> = Selector::new("app.main.clear_id_card");
-const CLEAR_SOCIAL_SECURITY_NUMBER: Selector<()> =
-    Selector::new("app.main.clear_social_security_number");
-const CLEAR_TAX_ID: Selector<()> = Selector::new("app.main.clear_tax_id");
-const CLEAR_POST_NUMBER: Selector<()> = Selector::new("app.main.clear_post_number");
-const CLEAR_BANK_ACCOUNT: Selector<String> = Selector::new("app.main.clear_bank_account");
-const REMOVE_KEY_VALUE_ITEM: Selector<String> = Selector::new("app.main.remove_key_value");
+/// Delete something from the profile, after the user confirmed it.
+const DELETE: Selector<Deletion> = Selector::new("app.main.delete");
+const TOGGLE_KEY_VALUE_SECRET: Selector<ItemId> = Selector::new("app.main.toggle_key_value_secret");
+/// Below this width, the sidebar collapses into a rail of icons.
+const SIDEBAR_BREAKPOINT: f64 = 700.0;
+const RAIL_WIDTH: f64 = 56.0;
+/// Heights of the rows in the lists of bank accounts and items.
+const ROW_HEIGHT: f64 = 60.0;
+const TAGGED_ROW_HEIGHT: f64 = 76.0;
+const CATEGORY_ROW_HEIGHT: f64 = 36.0;
 
-pub const PROFILE_UPDATED: Selector<Profile> = Selector::new("app.main.profile_updated");
+const TOGGLE_CATEGORY: Selector<String> = Selector::new("app.main.toggle_category");
+const FILTER_TAG: Selector<Option<String>> = Selector::new("app.main.filter_tag");
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum Process {
-    CreateTaxId,
-    CreatePostNumber,
-    CreateIdCard,
-    CreateBankAccount,
-    CreateSocialSecurityNumber,
-    CreateKeyValueItem,
-}
+pub const PROFILE_UPDATED: Selector<Profile> = Selector::new("app.main.profile_updated");
 
 pub struct MainController;
 
@@ -64,103 +84,25 @@ where
                 match data {
                     MainState::Home(state) => {
                         let process = cmd.get_unchecked(START_PROCESS);
-                        *data = match process {
-                            Process::CreateBankAccount => MainState::CreateBankAccount(
-                                CreateBankAccountState::from(state.clone()),
-                            ),
-                            Process::CreateIdCard => {
-                                MainState::CreateIdCard(CreateIdCardState::from(state.clone()))
-                            }
-                            Process::CreateTaxId => {
-                                MainState::CreateTaxId(CreateTaxIdState::from(state.clone()))
-                            }
-                            Process::CreatePostNumber => MainState::CreatePostNumber(
-                                CreatePostNumberState::from(state.clone()),
-                            ),
-                            Process::CreateSocialSecurityNumber => {
-                                MainState::CreateSocialSecurityNumber(
-                                    CreateSocialSecurityNumberState::from(state.clone()),
-                                )
-                            }
-                            Process::CreateKeyValueItem => MainState::CreateKeyValueItem(
-                                CreateKeyValueItemState::from(state.clone()),
-                            ),
+                        if let Some(process_state) = ProcessState::new(state.clone(), *process) {
+                            *data = MainState::Process(process_state);
                         }
                     }
                     _ => panic!("Cannot start a process when not in MainState::Home"),
                 }
                 ctx.set_handled();
             }
-            _ => {
-                child.event(ctx, event, data, env);
-            }
-        }
-    }
-}
-
-pub struct CreatePostNumberController;
-
-impl<W> Controller<CreatePostNumberState, W> for CreatePostNumberController
-where
-    W: Widget<CreatePostNumberState>,
-{
-    fn event(
-        &mut self,
-        child: &mut W,
-        ctx: &mut EventCtx,
-        event: &Event,
-        data: &mut CreatePostNumberState,
-        env: &Env,
-    ) {
-        match event {
-            Event::Notification(not) if not.is(create_post_number::CANCELED) => {
-                ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
-                ctx.set_handled();
-            }
-            Event::Notification(not) if not.is(create_post_number::CREATED) => {
-                let post_number = not.get(create_post_number::CREATED).unwrap();
-
-                let mut state = data.home_state.clone();
-                state.profile.post_number = Some(post_number.clone());
-                ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
-
-                ctx.submit_notification(GO_TO_HOME.with(state));
-                ctx.set_handled();
-            }
-            _ => {
-                child.event(ctx, event, data, env);
-            }
-        }
-    }
-}
-
-pub struct CreateTaxIdController;
-
-impl<W> Controller<CreateTaxIdState, W> for CreateTaxIdController
-where
-    W: Widget<CreateTaxIdState>,
-{
-    fn event(
-        &mut self,
-        child: &mut W,
-        ctx: &mut EventCtx,
-        event: &Event,
-        data: &mut CreateTaxIdState,
-        env: &Env,
-    ) {
-        match event {
-            Event::Notification(not) if not.is(create_tax_id::CANCELED) => {
-                ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
-                ctx.set_handled();
-            }
-            Event::Notification(not) if not.is(create_tax_id::CREATED) => {
-                let tax_id = not.get(create_tax_id::CREATED).unwrap();
-
-                let mut state = data.home_state.clone();
-                state.profile.tax_id = Some(tax_id.clone());
-                ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
-
-                ctx.submit_notification(GO_TO_HOME.with(state));
+            Event::Command(cmd) if cmd.is(EDIT_TEMPLATE) => {
+                match data {
+                    MainState::Home(state) => {
+                        let template_id = cmd.get_unchecked(EDIT_TEMPLATE);
+                        *data = MainState::TemplateEditor(TemplateEditorState::new(
+                            state.clone(),
+                            *template_id,
+                        ));
+                    }
+                    _ => panic!("Cannot edit a template when not in MainState::Home"),
+                }
                 ctx.set_handled();
             }
             _ => {
@@ -170,70 +112,41 @@ where
     }
 }
 
-pub struct CreateIdCardController;
+pub struct ProcessController;
 
-impl<W> Controller<CreateIdCardState, W> for CreateIdCardController
+impl<W> Controller<ProcessState, W> for ProcessController
 where
-    W: Widget<CreateIdCardState>,
+    W: Widget<ProcessState>,
 {
     fn event(
         &mut self,
         child: &mut W,
         ctx: &mut EventCtx,
         event: &Event,
-        data: &mut CreateIdCardState,
+        data: &mut ProcessState,
         env: &Env,
     ) {
         match event {
-            Event::Notification(not) if not.is(create_id_card::CANCELED) => {
+            Event::Notification(not) if not.is(form::CANCELED) => {
                 ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(create_id_card::CREATED) => {
-                let id_card = not.get(create_id_card::CREATED).unwrap();
-
+            Event::Notification(not) if not.is(form::SUBMITTED) => {
                 let mut state = data.home_state.clone();
-                state.profile.id_card = Some(id_card.clone());
-                ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
-
-                ctx.submit_notification(GO_TO_HOME.with(state));
-                ctx.set_handled();
-            }
-            _ => {
-                child.event(ctx, event, data, env);
-            }
-        }
-    }
-}
-
-pub struct CreateSocialSecurityNumberController;
-
-impl<W> Controller<CreateSocialSecurityNumberState, W> for CreateSocialSecurityNumberController
-where
-    W: Widget<CreateSocialSecurityNumberState>,
-{
-    fn event(
-        &mut self,
-        child: &mut W,
-        ctx: &mut EventCtx,
-        event: &Event,
-        data: &mut CreateSocialSecurityNumberState,
-        env: &Env,
-    ) {
-        match event {
-            Event::Notification(not) if not.is(create_social_security_number::CANCELED) => {
-                ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
-                ctx.set_handled();
-            }
-            Event::Notification(not) if not.is(create_social_security_number::CREATED) => {
-                let social_security_number =
-                    not.get(create_social_security_number::CREATED).unwrap();
-
-                let mut state = data.home_state.clone();
-                state.profile.social_security_number = Some(social_security_number.clone());
-                ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
+                let form = match data.process.form(&data.home_state.profile) {
+                    Some(form) => form,
+                    // The item was deleted, while the form was open.
+                    None => {
+                        ctx.submit_notification(GO_TO_HOME.with(state));
+                        ctx.set_handled();
+                        return;
+                    }
+                };
 
-                ctx.submit_notification(GO_TO_HOME.with(state));
+                if form.submit(&mut data.form_state, &mut state.profile) {
+                    ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
+                    ctx.submit_notification(GO_TO_HOME.with(state));
+                }
                 ctx.set_handled();
             }
             _ => {
@@ -243,33 +156,32 @@ where
     }
 }
 
-pub struct CreateBankAccountController;
+pub struct TemplateEditorController;
 
-impl<W> Controller<CreateBankAccountState, W> for CreateBankAccountController
+impl<W> Controller<TemplateEditorState, W> for TemplateEditorController
 where
-    W: Widget<CreateBankAccountState>,
+    W: Widget<TemplateEditorState>,
 {
     fn event(
         &mut self,
         child: &mut W,
         ctx: &mut EventCtx,
         event: &Event,
-        data: &mut CreateBankAccountState,
+        data: &mut TemplateEditorState,
         env: &Env,
     ) {
         match event {
-            Event::Notification(not) if not.is(create_bank_account::CANCELED) => {
+            Event::Notification(not) if not.is(form::CANCELED) => {
                 ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(create_bank_account::CREATED) => {
-                let bank_account = not.get(create_bank_account::CREATED).unwrap();
-
+            Event::Notification(not) if not.is(form::SUBMITTED) => {
                 let mut state = data.home_state.clone();
-                state.profile.bank_accounts.push_back(bank_account.clone());
-                ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
 
-                ctx.submit_notification(GO_TO_HOME.with(state));
+                if data.form_state.submit(&mut state.profile) {
+                    ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
+                    ctx.submit_notification(GO_TO_HOME.with(state));
+                }
                 ctx.set_handled();
             }
             _ => {
@@ -279,35 +191,25 @@ where
     }
 }
 
-pub struct CreateKeyValueItemController;
+pub struct ConflictController;
 
-impl<W> Controller<CreateKeyValueItemState, W> for CreateKeyValueItemController
+impl<W> Controller<ConflictState, W> for ConflictController
 where
-    W: Widget<CreateKeyValueItemState>,
+    W: Widget<ConflictState>,
 {
     fn event(
         &mut self,
         child: &mut W,
         ctx: &mut EventCtx,
         event: &Event,
-        data: &mut CreateKeyValueItemState,
+        data: &mut ConflictState,
         env: &Env,
     ) {
         match event {
-            Event::Notification(not) if not.is(create_key_value_item::CANCELED) => {
-                ctx.submit_notification(GO_TO_HOME.with(data.home_state.clone()));
-                ctx.set_handled();
-            }
-            Event::Notification(not) if not.is(create_key_value_item::CREATED) => {
-                let key_value_item = not.get(create_key_value_item::CREATED).unwrap();
+            Event::Notification(not) if not.is(conflict::RESOLVED) => {
+                let state = data.resolve(*not.get(conflict::RESOLVED).unwrap());
 
-                let mut state = data.home_state.clone();
-                state
-                    .profile
-                    .key_value_items
-                    .push_back(key_value_item.clone());
                 ctx.submit_notification(PROFILE_UPDATED.with(state.profile.get_profile()));
-
                 ctx.submit_notification(GO_TO_HOME.with(state));
                 ctx.set_handled();
             }
@@ -336,54 +238,70 @@ where
             Event::Command(cmd) if cmd.is(NAVIGATE) => {
                 let nav = cmd.get_unchecked(NAVIGATE);
                 data.nav = *nav;
+                if data.nav == Nav::Settings && data.settings.is_none() {
+                    data.settings = Some(SettingsState::from_env(env));
+                }
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(CLEAR_ID_CARD) => {
-                data.profile.id_card = None;
-                ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+            Event::Command(cmd) if cmd.is(CREATE_ON_PAGE) => {
+                if let Some(process) = page_process(data) {
+                    ctx.submit_command(START_PROCESS.with(process));
+                }
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(CLEAR_SOCIAL_SECURITY_NUMBER) => {
-                data.profile.social_security_number = None;
-                ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+            Event::Notification(not) if not.is(DELETE) => {
+                let deletion = not.get(DELETE).unwrap();
+
+                match data.confirm_deletions {
+                    true => {
+                        data.confirmation = Some(Confirmation {
+                            deletion: deletion.clone(),
+                            dialog: ConfirmState::new(data.profile.describe(deletion)),
+                        });
+                    }
+                    false => delete(ctx, data, deletion),
+                }
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(CLEAR_TAX_ID) => {
-                data.profile.tax_id = None;
-                ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+            Event::Notification(not) if not.is(CONFIRMED) => {
+                if let Some(confirmation) = data.confirmation.take() {
+                    if confirmation.dialog.dont_ask_again {
+                        data.confirm_deletions = false;
+                    }
+                    delete(ctx, data, &confirmation.deletion);
+                }
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(CLEAR_POST_NUMBER) => {
-                data.profile.post_number = None;
-                ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+            Event::Notification(not) if not.is(DISMISSED) => {
+                data.confirmation = None;
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(CLEAR_BANK_ACCOUNT) => {
-                let iban = not.get(CLEAR_BANK_ACCOUNT).unwrap();
+            Event::Notification(not) if not.is(TOGGLE_KEY_VALUE_SECRET) => {
+                let id = not.get(TOGGLE_KEY_VALUE_SECRET).unwrap();
 
-                data.profile.bank_accounts = data
-                    .profile
-                    .bank_accounts
-                    .iter()
-                    .filter(|account| account.iban != *iban)
-                    .map(|account| account.to_owned())
-                    .collect();
+                for item in data.profile.key_value_items.iter_mut() {
+                    if item.id == *id {
+                        item.secret = !item.secret;
+                    }
+                }
 
                 ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
                 ctx.set_handled();
             }
-            Event::Notification(not) if not.is(REMOVE_KEY_VALUE_ITEM) => {
-                let key = not.get(REMOVE_KEY_VALUE_ITEM).unwrap();
+            Event::Notification(not) if not.is(reorder::MOVE) => {
+                let (id, steps) = not.get(reorder::MOVE).unwrap();
 
-                data.profile.key_value_items = data
-                    .profile
-                    .key_value_items
-                    .iter()
-                    .filter(|item| item.key != *key)
-                    .map(|item| item.to_owned())
-                    .collect();
+                if data.profile.move_item(*id, *steps) {
+                    ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+                }
+                ctx.set_handled();
+            }
+            Event::Notification(not) if not.is(reorder::TOGGLE_PIN) => {
+                let id = not.get(reorder::TOGGLE_PIN).unwrap();
 
-                ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+                if data.profile.toggle_pin(*id) {
+                    ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+                }
                 ctx.set_handled();
             }
             _ => {
@@ -393,67 +311,172 @@ where
     }
 }
 
+fn delete(ctx: &mut EventCtx, data: &mut HomeState, deletion: &Deletion) {
+    data.profile.delete(deletion);
+    ctx.submit_notification(PROFILE_UPDATED.with(data.profile.get_profile()));
+}
+
 pub fn build() -> impl Widget<MainState> {
     MainState::matcher()
         .home(build_screen())
-        .create_id_card(
-            create_id_card::build()
-                .lens(CreateIdCardState::form_state)
-                .controller(CreateIdCardController),
-        )
-        .create_social_security_number(
-            create_social_security_number::build()
-                .lens(CreateSocialSecurityNumberState::form_state)
-                .controller(CreateSocialSecurityNumberController),
-        )
-        .create_tax_id(
-            create_tax_id::build()
-                .lens(CreateTaxIdState::form_state)
-                .controller(CreateTaxIdController),
-        )
-        .create_post_number(
-            create_post_number::build()
-                .lens(CreatePostNumberState::form_state)
-                .controller(CreatePostNumberController),
-        )
-        .create_bank_account(
-            create_bank_account::build()
-                .lens(CreateBankAccountState::form_state)
-                .controller(CreateBankAccountController),
-        )
-        .create_key_value_item(
-            create_key_value_item::build()
-                .lens(CreateKeyValueItemState::form_state)
-                .controller(CreateKeyValueItemController),
+        .process(build_process())
+        .template_editor(
+            template_editor::build()
+                .lens(TemplateEditorState::form_state)
+                .controller(TemplateEditorController),
         )
+        .conflict(conflict::build().controller(ConflictController))
         .controller(MainController)
 }
 
+fn build_process() -> impl Widget<ProcessState> {
+    ViewSwitcher::new(
+        |state: &ProcessState, _env| state.process,
+        |process, state: &ProcessState, _env| match process.form(&state.home_state.profile) {
+            Some(form) => Box::new(form.build().lens(ProcessState::form_state)),
+            None => Box::new(SizedBox::empty()),
+        },
+    )
+    .controller(ProcessController)
+}
+
+/// The create process of the current page. On the home page, this is the
+/// first document, which does not exist yet.
+fn page_process(state: &HomeState) -> Option<Process> {
+    match state.nav {
+        Nav::Home => document_types()
+            .iter()
+            .find(|document_type| state.profile.document(document_type.id()).is_none())
+            .map(|document_type| Process::CreateDocument(document_type.id())),
+        Nav::BankAccounts => Some(Process::CreateBankAccount),
+        Nav::Miscellaneous => Some(Process::CreateKeyValueItem),
+        Nav::History | Nav::Settings => None,
+    }
+}
+
 fn build_screen() -> impl Widget<HomeState> {
     let sidebar = Flex::column()
         .must_fill_main_axis(true)
         .with_child(build_sidebar_header().lens(HomeState::profile.then(ProfileState::name)))
         .with_child(build_sidebar_navigation())
-        .background(theme::BACKGROUND_LIGHT);
-
-    let main = ViewSwitcher::new(
-        |state: &HomeState, _env| state.nav,
-        |nav, _state, _env| match nav {
-            Nav::Home => Box::new(build_home().lens(HomeState::profile)),
-            Nav::BankAccounts => Box::new(build_bank_account_page().lens(HomeState::profile)),
-            Nav::Miscellaneous => Box::new(build_miscellaneous_page().lens(HomeState::profile)),
-        },
+        .background(theme::SURFACE);
+
+    let main = Flex::column()
+        .with_child(build_expiry_banner().lens(HomeState::profile))
+        .with_flex_child(
+            ViewSwitcher::new(
+                |state: &HomeState, _env| state.nav,
+                |nav, _state, _env| match nav {
+                    Nav::Home => Box::new(build_home().lens(HomeState::profile)),
+                    Nav::BankAccounts => {
+                        Box::new(build_bank_account_page().lens(HomeState::profile))
+                    }
+                    Nav::Miscellaneous => Box::new(build_miscellaneous_page()),
+                    Nav::History => Box::new(history::build_page()),
+                    Nav::Settings => {
+                        Box::new(Maybe::or_empty(settings::build).lens(HomeState::settings))
+                    }
+                },
+            ),
+            1.0,
+        )
+        .background(theme::BACKGROUND)
+        .expand();
+
+    let screen = palette::build(
+        SidebarLayout::new(sidebar, build_sidebar_rail(), main).breakpoint(SIDEBAR_BREAKPOINT),
+    );
+
+    Overlay::new(
+        screen,
+        ViewSwitcher::new(
+            |state: &HomeState, _env| state.confirmation.is_some(),
+            |is_open, _state, _env| match is_open {
+                true => Box::new(
+                    confirm_dialog(&i18n::text("delete-title"), &i18n::text("delete")).lens(
+                        HomeState::confirmation
+                            .then(SomeLens)
+                            .then(Confirmation::dialog),
+                    ),
+                ),
+                false => Box::new(SizedBox::empty()),
+            },
+        ),
+        |state: &HomeState| state.confirmation.is_some(),
+    )
+    .controller(HomeController)
+}
+
+/// Warning for all documents, which expire within the configured
+/// `EXPIRY_WARNING_DAYS`.
+fn build_expiry_banner() -> impl Widget<ProfileState> {
+    Either::new(
+        |state: &ProfileState, env| !due_expiries(state, env).is_empty(),
+        Label::dynamic(|state: &ProfileState, env| {
+            due_expiries(state, env)
+                .iter()
+                .map(|expiry| {
+                    format!(
+                        "{}: {}",
+                        i18n::text(expiry.name),
+                        describe_expiry(expiry, expiry_warning_window(env), env)
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .with_line_break_mode(LineBreaking::WordWrap)
+        .with_text_color(theme::ON_STATUS)
+        .expand_width()
+        .padding(10.0)
+        .background(theme::WARNING)
+        .rounded(4.0)
+        .padding(10.0),
+        SizedBox::empty(),
     )
-    .background(theme::BACKGROUND_DARK)
-    .expand();
+}
+
+fn due_expiries(state: &ProfileState, env: &Env) -> Vec<Expiry> {
+    let window = expiry_warning_window(env);
+    let today = today();
 
-    Split::columns(sidebar, main)
-        .split_point(0.3)
-        .bar_size(1.0)
-        .min_size(150.0, 300.0)
-        .min_bar_area(1.0)
-        .solid_bar(true)
-        .controller(HomeController)
+    state
+        .expiries()
+        .into_iter()
+        .filter(|expiry| expiry.is_due(window, today))
+        .collect()
+}
+
+fn expiry_warning_window(env: &Env) -> Duration {
+    Duration::days(env.get(EXPIRY_WARNING_DAYS) as i64)
+}
+
+/// Human readable description of the validity of a document, e.g.
+/// `abgelaufen seit 12 Tagen` or `gültig bis 03.2031`.
+fn describe_expiry(expiry: &Expiry, threshold: Duration, env: &Env) -> String {
+    let today = today();
+    let days = expiry.time_until_expiration(today).num_days();
+
+    match expiry.status(threshold, today) {
+        ExpiryStatus::Expired => i18n::text_with("expiry-expired", &[("days", (-days).into())]),
+        ExpiryStatus::ExpiringSoon if days == 0 => i18n::text("expiry-today"),
+        ExpiryStatus::ExpiringSoon => i18n::text_with("expiry-soon", &[("days", days.into())]),
+        ExpiryStatus::Valid => i18n::text_with(
+            "expiry-valid",
+            &[(
+                "date",
+                date_format(env).format_month(expiry.valid_until).into(),
+            )],
+        ),
+    }
+}
+
+fn status_color(status: ExpiryStatus, env: &Env) -> Color {
+    match status {
+        ExpiryStatus::Expired => env.get(theme::DANGER),
+        ExpiryStatus::ExpiringSoon => env.get(theme::WARNING),
+        ExpiryStatus::Valid => env.get(theme::SUCCESS),
+    }
 }
 
 fn build_sidebar_header() -> impl Widget<Name> {
@@ -483,72 +506,140 @@ fn build_sidebar_header() -> impl Widget<Name> {
 fn build_sidebar_navigation() -> impl Widget<HomeState> {
     Flex::column()
         .with_default_spacer()
-        .with_child(sidebar_link_widget("Basisdaten", Nav::Home))
-        .with_child(sidebar_link_widget("Konten", Nav::BankAccounts))
-        .with_child(sidebar_link_widget("Sonstiges", Nav::Miscellaneous))
+        .with_child(sidebar_link_widget("nav-home", Nav::Home))
+        .with_child(sidebar_link_widget("nav-bank-accounts", Nav::BankAccounts))
+        .with_child(sidebar_link_widget("nav-miscellaneous", Nav::Miscellaneous))
+        .with_child(sidebar_link_widget("nav-history", Nav::History))
+        .with_child(sidebar_link_widget("nav-settings", Nav::Settings))
 }
 
-fn sidebar_link_widget(title: &str, link_nav: Nav) -> impl Widget<HomeState> {
-    Label::new(title)
-        .with_text_size(20.0)
-        .expand_width()
+/// Collapsed sidebar with an icon for each page.
+fn build_sidebar_rail() -> impl Widget<HomeState> {
+    Flex::column()
+        .must_fill_main_axis(true)
+        .with_default_spacer()
+        .with_child(rail_link_widget("⌂", Nav::Home))
+        .with_child(rail_link_widget("€", Nav::BankAccounts))
+        .with_child(rail_link_widget("≡", Nav::Miscellaneous))
+        .with_child(rail_link_widget("↺", Nav::History))
+        .with_child(rail_link_widget("⚙", Nav::Settings))
+        .fix_width(RAIL_WIDTH)
+        .background(theme::SURFACE)
+}
+
+fn rail_link_widget(icon: &str, link_nav: Nav) -> impl Widget<HomeState> {
+    Label::new(icon)
+        .with_text_size(22.0)
+        .with_text_color(theme::MUTED_TEXT)
+        .center()
         .lens(HomeState::nav)
+        .padding((0.0, 10.0))
+        .on_click(move |ctx, _, _| ctx.submit_command(NAVIGATE.with(link_nav)))
+}
+
+/// Link to a page with the number of its entries, `title` is a message id.
+fn sidebar_link_widget(title: &str, link_nav: Nav) -> impl Widget<HomeState> {
+    Flex::row()
+        .with_flex_child(
+            Label::new(i18n::text(title))
+                .with_text_size(20.0)
+                .expand_width(),
+            1.0,
+        )
+        .with_child(Either::new(
+            move |state: &HomeState, _env| page_count(state, link_nav).is_some(),
+            badge(
+                move |state: &HomeState, _env| {
+                    page_count(state, link_nav).unwrap_or_default().to_string()
+                },
+                |_state: &HomeState, env| env.get(theme::MUTED_TEXT),
+            ),
+            SizedBox::empty(),
+        ))
         .padding((25.0, 10.0))
         .on_click(move |ctx, _, _| ctx.submit_command(NAVIGATE.with(link_nav)))
 }
 
+/// Number of documents, bank accounts or items on a page.
+fn page_count(state: &HomeState, nav: Nav) -> Option<usize> {
+    let profile = &state.profile;
+
+    match nav {
+        Nav::Home => Some(profile.documents.len()),
+        Nav::BankAccounts => Some(profile.bank_accounts.len()),
+        Nav::Miscellaneous => Some(profile.key_value_items.len()),
+        Nav::History | Nav::Settings => None,
+    }
+}
+
+/// Ids of the document types in the order they are shown on the home page,
+/// the most urgent first.
+fn home_order(state: &ProfileState, env: &Env) -> Vector<&'static str> {
+    let mut document_types = document_types().to_vec();
+    sort_by_expiry(
+        &mut document_types,
+        &state.get_profile(),
+        expiry_warning_window(env),
+        today(),
+    );
+
+    document_types
+        .iter()
+        .map(|document_type| document_type.id())
+        .collect()
+}
+
 fn build_home() -> impl Widget<ProfileState> {
-    Flex::column()
+    let documents = ViewSwitcher::new(home_order, |order, _state, _env| {
+        let mut documents = Flex::column().cross_axis_alignment(CrossAxisAlignment::Start);
+        for id in order.iter().copied() {
+            let document_type = document_type(id).expect("Document type is not registered");
+
+            documents.add_child(build_optional_document(document_type).lens(Map::new(
+                move |state: &ProfileState| state.document(id),
+                move |state: &mut ProfileState, document| state.set_document(id, document),
+            )));
+            documents.add_default_spacer();
+        }
+
+        Box::new(documents)
+    });
+
+    let documents = Flex::column()
         .cross_axis_alignment(CrossAxisAlignment::Start)
-        .with_child(build_optional_id_card().lens(ProfileState::id_card))
-        .with_default_spacer()
-        .with_child(
-            build_optional_item(
-                String::from("Sozialversichersungsnummer"),
-                |ctx, _state, _env| {
-                    ctx.submit_command(START_PROCESS.with(Process::CreateSocialSecurityNumber))
-                },
-                |ctx, _, _| ctx.submit_notification(CLEAR_SOCIAL_SECURITY_NUMBER),
-            )
-            .lens(ProfileState::social_security_number),
-        )
-        .with_default_spacer()
-        .with_child(
-            build_optional_item(
-                String::from("Steuer-ID"),
-                |ctx, _state, _env| ctx.submit_command(START_PROCESS.with(Process::CreateTaxId)),
-                |ctx, _, _| ctx.submit_notification(CLEAR_TAX_ID),
-            )
-            .lens(ProfileState::tax_id),
-        )
-        .with_default_spacer()
+        .with_child(documents)
         .with_child(
-            build_optional_item(
-                String::from("Postnummer"),
-                |ctx, _state, _env| {
-                    ctx.submit_command(START_PROCESS.with(Process::CreatePostNumber))
-                },
-                |ctx, _, _| ctx.submit_notification(CLEAR_POST_NUMBER),
-            )
-            .lens(ProfileState::post_number),
+            OutlineButton::new(i18n::text("calendar-export"))
+                .on_click(|ctx, state: &mut ProfileState, _env| {
+                    ctx.submit_command(EXPORT_CALENDAR.with(state.get_profile()))
+                })
+                .padding(10.0),
         )
-        .padding(10.0)
-        .expand()
+        .padding(10.0);
+
+    Scroll::new(documents).vertical().expand()
 }
 
-fn build_optional_id_card() -> impl Widget<Option<IdCard>> {
+fn build_optional_document(
+    document_type: &'static dyn DocumentType,
+) -> impl Widget<Option<Document>> {
     ViewSwitcher::new(
-        |state: &Option<IdCard>, _env| state.is_some(),
+        |state: &Option<Document>, _env| state.is_some(),
         move |state, _state, _env| match state {
-            true => Box::new(build_id_card_item().lens(SomeLens)),
-            false => Box::new(build_add_button("Personalausweis".into(), |ctx, _, _| {
-                ctx.submit_command(START_PROCESS.with(Process::CreateIdCard))
-            })),
+            true => Box::new(build_document(document_type).lens(SomeLens)),
+            false => Box::new(build_add_button(
+                &i18n::text(document_type.name()),
+                move |ctx, _, _| {
+                    ctx.submit_command(
+                        START_PROCESS.with(Process::CreateDocument(document_type.id())),
+                    )
+                },
+            )),
         },
     )
 }
 
-fn build_id_card_item() -> impl Widget<IdCard> {
+fn build_document(document_type: &'static dyn DocumentType) -> impl Widget<Document> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Center)
         .main_axis_alignment(MainAxisAlignment::SpaceBetween)
@@ -556,50 +647,53 @@ fn build_id_card_item() -> impl Widget<IdCard> {
         .with_child(
             Flex::column()
                 .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(SecretLabel::new(
+                    move |state: &Document, _env| document_type.format(state),
+                    move |_state: &Document, _env| document_type.is_secret(),
+                ))
                 .with_child(
-                    Flex::row().with_child(Label::dynamic(|state: &IdCard, _env| {
-                        state.card_number.to_string()
-                    })),
-                )
-                .with_child(
-                    Label::dynamic(|state: &IdCard, _env| {
-                        format!(
-                            "Personalsausweis - {} Tage gültig",
-                            state.time_until_expiration().num_days()
+                    Flex::row()
+                        .with_child(
+                            Label::new(i18n::text(document_type.name())).with_text_size(12.0),
                         )
-                    })
-                    .with_text_size(12.0),
+                        .with_spacer(8.0)
+                        .with_child(Either::new(
+                            |state: &Document, _env| state.expiry().is_some(),
+                            badge(
+                                |state: &Document, env| {
+                                    describe_expiry(
+                                        &state.expiry().unwrap(),
+                                        expiry_warning_window(env),
+                                        env,
+                                    )
+                                },
+                                |state: &Document, env| {
+                                    let expiry = state.expiry().unwrap();
+                                    let status = expiry.status(expiry_warning_window(env), today());
+                                    status_color(status, env)
+                                },
+                            ),
+                            SizedBox::empty(),
+                        )),
                 ),
         )
         .with_flex_spacer(1.0)
         .with_child(
-            OutlineButton::new("Löschen")
-                .on_click(|ctx, _, _| ctx.submit_notification(CLEAR_ID_CARD)),
+            OutlineButton::new(i18n::text("delete"))
+                .danger()
+                .on_click(move |ctx, _, _| {
+                    ctx.submit_notification(DELETE.with(Deletion::Document(document_type.id())))
+                }),
         )
         .with_default_spacer()
-        .with_child(OutlineButton::new("Kopieren").on_click(
-            move |_ctx, state: &mut IdCard, _env| copy_to_clipboard(state.card_number.to_string()),
+        .with_child(OutlineButton::new(i18n::text("copy")).on_click(
+            move |ctx, state: &mut Document, _env| {
+                copy_to_clipboard(ctx, document_type.copy_value(state))
+            },
         ))
         .padding(10.0)
 }
 
-fn build_optional_item<T>(
-    title: String,
-    on_create: impl Fn(&mut EventCtx, &mut Option<T>, &Env) + 'static + Copy,
-    on_delete: impl Fn(&mut EventCtx, &mut T, &Env) + 'static + Copy,
-) -> impl Widget<Option<T>>
-where
-    T: Into<String> + druid::Data,
-{
-    ViewSwitcher::new(
-        |state: &Option<T>, _env| state.is_some(),
-        move |state, _state, _env| match state {
-            true => Box::new(build_item(&title, on_delete).lens(SomeLens)),
-            false => Box::new(build_add_button(&title, on_create)),
-        },
-    )
-}
-
 fn build_add_button<T>(
     title: &str,
     on_create: impl Fn(&mut EventCtx, &mut Option<T>, &Env) + 'static,
@@ -617,131 +711,461 @@ where
                 .with_child(Flex::row().with_child(Label::new("...")))
                 .with_child(Label::new(title).with_text_size(12.0)),
         )
-        .with_child(OutlineButton::new("Erstellen").on_click(on_create))
+        .with_child(OutlineButton::new(i18n::text("create")).on_click(on_create))
         .padding(10.0)
 }
 
-fn build_item<T>(
-    title: &str,
-    on_delete: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
-) -> impl Widget<T>
-where
-    T: Into<String> + druid::Data,
-{
+fn build_bank_account_page() -> impl Widget<ProfileState> {
+    VirtualList::new(
+        |state: &ProfileState| state.bank_accounts.clone(),
+        || reorder::row(build_bank_account()),
+        |_account: &BankAccount| ROW_HEIGHT,
+    )
+    .footer(Flex::column().with_spacer(20.0).with_child(
+        OutlineButton::new(i18n::text("bank-account-new")).on_click(|ctx, _, _| {
+            ctx.submit_command(START_PROCESS.with(Process::CreateBankAccount))
+        }),
+    ))
+    .padding(10.0)
+}
+
+fn build_bank_account() -> impl Widget<BankAccount> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Center)
-        .main_axis_alignment(MainAxisAlignment::SpaceBetween)
         .must_fill_main_axis(true)
         .with_child(
             Flex::column()
                 .cross_axis_alignment(CrossAxisAlignment::Start)
-                .with_child(Label::new(move |state: &T, _env: &_| state.clone().into()))
-                .with_child(Label::new(title).with_text_size(12.0)),
+                .with_child(SecretLabel::new(
+                    |account: &BankAccount, _env| account.iban.clone(),
+                    |_account: &BankAccount, _env| true,
+                ))
+                .with_child(
+                    Label::dynamic(|account: &BankAccount, _env| account.name.clone())
+                        .with_text_size(12.0),
+                ),
         )
         .with_flex_spacer(1.0)
-        .with_child(
-            OutlineButton::new("Löschen")
-                .on_click(move |ctx, state: &mut T, env| on_delete(ctx, state, env)),
-        )
+        .with_child(reorder::pin_button())
+        .with_default_spacer()
+        .with_child(OutlineButton::new(i18n::text("delete")).danger().on_click(
+            |ctx, account: &mut BankAccount, _| {
+                ctx.submit_notification(DELETE.with(Deletion::BankAccount(account.id)))
+            },
+        ))
         .with_default_spacer()
         .with_child(
-            OutlineButton::new("Kopieren")
-                .on_click(move |_ctx, state: &mut T, _env| copy_to_clipboard(state.clone().into())),
+            OutlineButton::new(i18n::text("bank-account-copy-iban")).on_click(
+                |ctx, account: &mut BankAccount, _env| copy_to_clipboard(ctx, &account.iban),
+            ),
         )
         .padding(10.0)
 }
 
-fn build_bank_account_page() -> impl Widget<ProfileState> {
-    Flex::column()
-        .with_flex_child(
-            List::new(|| build_bank_account())
+/// Row of the list on the "Sonstiges" page.
+#[derive(Clone, Data, Matcher)]
+pub enum MiscRow {
+    Category(CategoryHeader),
+    Item(KeyValueItem),
+}
+
+#[derive(Clone, Data)]
+pub struct CategoryHeader {
+    name: String,
+    /// Number of items, also of a collapsed category.
+    count: usize,
+    is_collapsed: bool,
+}
+
+#[derive(Clone, Data)]
+struct TagChip {
+    /// `None` shows all items.
+    tag: Option<String>,
+    is_selected: bool,
+}
+
+/// Collapses the categories and filters the items by tag.
+struct MiscellaneousController;
+
+impl<W> Controller<HomeState, W> for MiscellaneousController
+where
+    W: Widget<HomeState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut HomeState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Notification(not) if not.is(TOGGLE_CATEGORY) => {
+                let category = not.get(TOGGLE_CATEGORY).unwrap();
+                let collapsed = &mut data.miscellaneous.collapsed;
+
+                match collapsed.iter().position(|other| other == category) {
+                    Some(index) => {
+                        collapsed.remove(index);
+                    }
+                    None => collapsed.push_back(category.clone()),
+                }
+                ctx.set_handled();
+            }
+            Event::Notification(not) if not.is(FILTER_TAG) => {
+                data.miscellaneous.tag_filter = not.get(FILTER_TAG).unwrap().clone();
+                ctx.set_handled();
+            }
+            _ => {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+}
+
+/// The selected tag, unless it was removed from all items.
+fn tag_filter(state: &HomeState) -> Option<&str> {
+    state.miscellaneous.tag_filter.as_deref().filter(|tag| {
+        state
+            .profile
+            .key_value_items
+            .iter()
+            .any(|item| item.has_tag(tag))
+    })
+}
+
+/// A header for each category, followed by its items, unless it is
+/// collapsed.
+fn miscellaneous_rows(state: &HomeState) -> Vector<MiscRow> {
+    let tag = tag_filter(state);
+    let items = state
+        .profile
+        .key_value_items
+        .iter()
+        .filter(|item| match tag {
+            Some(tag) => item.has_tag(tag),
+            None => true,
+        });
+
+    let mut rows = Vector::new();
+    for (name, items) in group_by_category(items) {
+        let is_collapsed = state
+            .miscellaneous
+            .collapsed
+            .iter()
+            .any(|other| *other == name);
+
+        rows.push_back(MiscRow::Category(CategoryHeader {
+            name,
+            count: items.len(),
+            is_collapsed,
+        }));
+        if !is_collapsed {
+            rows.extend(items.into_iter().map(MiscRow::Item));
+        }
+    }
+
+    rows
+}
+
+fn miscellaneous_row_height(row: &MiscRow) -> f64 {
+    match row {
+        MiscRow::Category(_) => CATEGORY_ROW_HEIGHT,
+        MiscRow::Item(item) if !item.tags.is_empty() => TAGGED_ROW_HEIGHT,
+        MiscRow::Item(_) => ROW_HEIGHT,
+    }
+}
+
+/// A chip for all items and one per tag, if any item has a tag.
+fn tag_chips(state: &HomeState) -> Arc<Vec<TagChip>> {
+    let tags = all_tags(state.profile.key_value_items.iter());
+    if tags.is_empty() {
+        return Arc::new(Vec::new());
+    }
+
+    let selected = tag_filter(state);
+    let chips = iter::once(None)
+        .chain(tags.into_iter().map(Some))
+        .map(|tag| TagChip {
+            is_selected: tag.as_deref() == selected,
+            tag,
+        })
+        .collect();
+
+    Arc::new(chips)
+}
+
+fn build_miscellaneous_page() -> impl Widget<HomeState> {
+    let footer = Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_default_spacer()
+        .with_child(
+            OutlineButton::new(i18n::text("create")).on_click(|ctx, _, _| {
+                ctx.submit_command(START_PROCESS.with(Process::CreateKeyValueItem))
+            }),
+        )
+        .with_spacer(20.0)
+        .with_child(
+            List::new(|| build_entry())
                 .with_spacing(10.0)
-                .lens(ProfileState::bank_accounts),
-            1.0,
+                .lens(HomeState::profile.then(ProfileState::entries)),
         )
+        .with_spacer(20.0)
+        .with_child(Label::new(i18n::text("templates")).with_text_size(20.0))
         .with_default_spacer()
+        .with_child(
+            List::new(|| build_template())
+                .with_spacing(10.0)
+                .lens(HomeState::profile.then(ProfileState::templates)),
+        )
         .with_default_spacer()
         .with_child(
-            OutlineButton::new("Neues Konto Erstellen").on_click(|ctx, _, _| {
-                ctx.submit_command(START_PROCESS.with(Process::CreateBankAccount))
-            }),
+            OutlineButton::new(i18n::text("template-create"))
+                .on_click(|ctx, _, _| ctx.submit_command(EDIT_TEMPLATE.with(None))),
+        );
+
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            List::new(build_tag_chip)
+                .horizontal()
+                .with_spacing(5.0)
+                .lens(Map::new(
+                    tag_chips,
+                    |_state: &mut HomeState, _chips: Arc<Vec<TagChip>>| {},
+                )),
+        )
+        .with_default_spacer()
+        .with_flex_child(
+            VirtualList::new(
+                miscellaneous_rows,
+                build_miscellaneous_row,
+                miscellaneous_row_height,
+            )
+            .sticky_headers(|row| matches!(row, MiscRow::Category(_)))
+            .footer(footer),
+            1.0,
         )
         .padding(10.0)
+        .controller(MiscellaneousController)
 }
 
-fn build_bank_account() -> impl Widget<BankAccount> {
+fn build_tag_chip() -> impl Widget<TagChip> {
+    Label::dynamic(|chip: &TagChip, _env| match &chip.tag {
+        Some(tag) => format!("#{}", tag),
+        None => i18n::text("tags-all"),
+    })
+    .with_text_size(12.0)
+    .padding((8.0, 2.0))
+    .background(Painter::new(|ctx, chip: &TagChip, env| {
+        let rect = ctx.size().to_rounded_rect(10.0);
+        match chip.is_selected {
+            true => ctx.fill(rect, &env.get(theme::SELECTED)),
+            false => ctx.stroke(rect, &env.get(theme::BORDER), 1.0),
+        }
+    }))
+    .on_click(|ctx, chip: &mut TagChip, _env| {
+        ctx.submit_notification(FILTER_TAG.with(chip.tag.clone()))
+    })
+}
+
+fn build_miscellaneous_row() -> impl Widget<MiscRow> {
+    MiscRow::matcher()
+        .category(build_category_header())
+        .item(reorder::row(build_key_value_item()))
+}
+
+/// Header of a category, which collapses it when clicked.
+fn build_category_header() -> impl Widget<CategoryHeader> {
+    Label::dynamic(|header: &CategoryHeader, _env| {
+        let arrow = match header.is_collapsed {
+            true => "▸",
+            false => "▾",
+        };
+        let name = match header.name.as_str() {
+            UNCATEGORIZED => i18n::text("category-none"),
+            name => String::from(name),
+        };
+        format!("{} {} ({})", arrow, name, header.count)
+    })
+    .with_text_size(16.0)
+    .align_left()
+    .on_click(|ctx, header: &mut CategoryHeader, _env| {
+        ctx.submit_notification(TOGGLE_CATEGORY.with(header.name.clone()))
+    })
+}
+
+fn build_template() -> impl Widget<Template> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Center)
         .must_fill_main_axis(true)
         .with_child(
             Flex::column()
                 .cross_axis_alignment(CrossAxisAlignment::Start)
-                .with_child(Label::dynamic(|account: &BankAccount, _env| {
-                    account.iban.clone()
+                .with_child(Label::dynamic(|template: &Template, _env| {
+                    template.name.clone()
                 }))
                 .with_child(
-                    Label::dynamic(|account: &BankAccount, _env| account.name.clone())
-                        .with_text_size(12.0),
+                    Label::dynamic(|template: &Template, _env| {
+                        let labels: Vec<&str> = template
+                            .fields
+                            .iter()
+                            .map(|field| field.label.as_str())
+                            .collect();
+                        labels.join(", ")
+                    })
+                    .with_text_size(12.0),
                 ),
         )
         .with_flex_spacer(1.0)
-        .with_child(
-            OutlineButton::new("Löschen").on_click(|ctx, account: &mut BankAccount, _| {
-                ctx.submit_notification(CLEAR_BANK_ACCOUNT.with(account.iban.to_owned()))
-            }),
-        )
+        .with_child(OutlineButton::new(i18n::text("entry-new")).on_click(
+            |ctx, template: &mut Template, _| {
+                ctx.submit_command(START_PROCESS.with(Process::CreateEntry(template.id)))
+            },
+        ))
         .with_default_spacer()
-        .with_child(
-            OutlineButton::new("IBAN Kopieren")
-                .on_click(|_ctx, account: &mut BankAccount, _env| copy_to_clipboard(&account.iban)),
-        )
+        .with_child(OutlineButton::new(i18n::text("edit")).on_click(
+            |ctx, template: &mut Template, _| {
+                ctx.submit_command(EDIT_TEMPLATE.with(Some(template.id)))
+            },
+        ))
+        .with_default_spacer()
+        .with_child(OutlineButton::new(i18n::text("delete")).danger().on_click(
+            |ctx, template: &mut Template, _| {
+                ctx.submit_notification(DELETE.with(Deletion::Template(template.id)))
+            },
+        ))
         .padding(10.0)
 }
 
-fn build_miscellaneous_page() -> impl Widget<ProfileState> {
+fn build_entry() -> impl Widget<Entry> {
     Flex::column()
-        .with_flex_child(
-            List::new(|| build_key_value_item())
-                .with_spacing(10.0)
-                .lens(ProfileState::key_value_items),
-            1.0,
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            Flex::row()
+                .cross_axis_alignment(CrossAxisAlignment::Center)
+                .must_fill_main_axis(true)
+                .with_child(
+                    Label::dynamic(|entry: &Entry, _env| entry.name.clone()).with_text_size(16.0),
+                )
+                .with_flex_spacer(1.0)
+                .with_child(OutlineButton::new(i18n::text("delete")).danger().on_click(
+                    |ctx, entry: &mut Entry, _| {
+                        ctx.submit_notification(DELETE.with(Deletion::Entry(entry.id)))
+                    },
+                )),
         )
         .with_default_spacer()
-        .with_default_spacer()
-        .with_child(OutlineButton::new("Erstellen").on_click(|ctx, _, _| {
-            ctx.submit_command(START_PROCESS.with(Process::CreateKeyValueItem))
-        }))
+        .with_child(
+            List::new(|| build_entry_value())
+                .with_spacing(5.0)
+                .lens(Map::new(
+                    |entry: &Entry| Arc::new(entry.values.clone()),
+                    |entry: &mut Entry, values: Arc<Vec<EntryValue>>| {
+                        entry.values = values.to_vec()
+                    },
+                )),
+        )
         .padding(10.0)
+        .border(theme::BORDER, 1.0)
+        .rounded(4.0)
 }
 
-fn build_key_value_item() -> impl Widget<KeyValueItem> {
+fn build_entry_value() -> impl Widget<EntryValue> {
     Flex::row()
         .cross_axis_alignment(CrossAxisAlignment::Center)
         .must_fill_main_axis(true)
         .with_child(
             Flex::column()
                 .cross_axis_alignment(CrossAxisAlignment::Start)
-                .with_child(Label::dynamic(|item: &KeyValueItem, _env| item.key.clone()))
+                .with_child(SecretLabel::new(
+                    |value: &EntryValue, env| format_entry_value(value, env),
+                    |value: &EntryValue, _env| value.kind == TemplateFieldKind::Secret,
+                ))
                 .with_child(
-                    Label::dynamic(|item: &KeyValueItem, _env| item.value.clone())
+                    Label::dynamic(|value: &EntryValue, _env| value.label.clone())
                         .with_text_size(12.0),
                 ),
         )
         .with_flex_spacer(1.0)
         .with_child(
-            OutlineButton::new("Kopieren")
-                .on_click(|_ctx, item: &mut KeyValueItem, _env| copy_to_clipboard(&item.value)),
+            OutlineButton::new(i18n::text("copy"))
+                .on_click(|ctx, value: &mut EntryValue, _env| copy_to_clipboard(ctx, &value.value)),
         )
+        .padding((10.0, 0.0))
+}
+
+/// Show dates in the configured format.
+fn format_entry_value(value: &EntryValue, env: &Env) -> String {
+    match value.kind {
+        TemplateFieldKind::Date => NaiveDate::from_str(&value.value)
+            .map(|date| format_date(date, env))
+            .unwrap_or_else(|_| value.value.clone()),
+        _ => value.value.clone(),
+    }
+}
+
+fn build_key_value_item() -> impl Widget<KeyValueItem> {
+    Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .must_fill_main_axis(true)
+        .with_child(
+            Flex::column()
+                .cross_axis_alignment(CrossAxisAlignment::Start)
+                .with_child(Label::dynamic(|item: &KeyValueItem, _env| item.key.clone()))
+                .with_child(
+                    SecretLabel::new(
+                        |item: &KeyValueItem, _env| item.value.clone(),
+                        |item: &KeyValueItem, _env| item.secret,
+                    )
+                    .with_text_size(12.0),
+                )
+                .with_child(Either::new(
+                    |item: &KeyValueItem, _env| item.tags.is_empty(),
+                    SizedBox::empty(),
+                    Label::dynamic(|item: &KeyValueItem, _env| {
+                        let tags: Vec<String> =
+                            item.tags.iter().map(|tag| format!("#{}", tag)).collect();
+                        tags.join(" ")
+                    })
+                    .with_text_color(theme::MUTED_TEXT)
+                    .with_text_size(12.0),
+                )),
+        )
+        .with_flex_spacer(1.0)
+        .with_child(reorder::pin_button())
         .with_default_spacer()
         .with_child(
-            OutlineButton::new("Löschen").on_click(|ctx, item: &mut KeyValueItem, _| {
-                ctx.submit_notification(REMOVE_KEY_VALUE_ITEM.with(item.key.to_owned()))
+            OutlineButton::new(|item: &KeyValueItem, _env: &Env| match item.secret {
+                true => i18n::text("item-secret-yes"),
+                false => i18n::text("item-secret-no"),
+            })
+            .on_click(|ctx, item: &mut KeyValueItem, _| {
+                ctx.submit_notification(TOGGLE_KEY_VALUE_SECRET.with(item.id))
             }),
         )
+        .with_default_spacer()
+        .with_child(
+            OutlineButton::new(i18n::text("copy"))
+                .on_click(|ctx, item: &mut KeyValueItem, _env| copy_to_clipboard(ctx, &item.value)),
+        )
+        .with_default_spacer()
+        .with_child(OutlineButton::new(i18n::text("edit")).on_click(
+            |ctx, item: &mut KeyValueItem, _env| {
+                ctx.submit_command(START_PROCESS.with(Process::EditKeyValueItem(item.id)))
+            },
+        ))
+        .with_default_spacer()
+        .with_child(OutlineButton::new(i18n::text("delete")).danger().on_click(
+            |ctx, item: &mut KeyValueItem, _| {
+                ctx.submit_notification(DELETE.with(Deletion::KeyValueItem(item.id)))
+            },
+        ))
         .padding(10.0)
 }
 
-fn copy_to_clipboard(value: impl Into<String>) {
-    let mut clipboard = Application::global().clipboard();
-    clipboard.put_string(value.into());
+/// Copy the value, the clipboard is cleared again after the configured
+/// timeout.
+fn copy_to_clipboard(ctx: &mut EventCtx, value: impl Into<String>) {
+    ctx.submit_command(COPY.with(value.into()));
 }
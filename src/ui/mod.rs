@@ -1,21 +1,40 @@
-pub mod create_bank_account;
-pub mod create_id_card;
-pub mod create_key_value_item;
-pub mod create_post_number;
+mod clipboard_toast;
+mod conflict;
+pub mod create;
 pub mod create_profile;
-pub mod create_social_security_number;
-pub mod create_tax_id;
+pub mod form;
+mod history;
 mod main;
+mod palette;
+mod reorder;
+pub mod settings;
+mod shortcuts;
 mod some_lens;
+pub mod template_editor;
+mod window;
 
-use crate::data::Profile;
+use crate::data::{Profile, Theme};
 use crate::state::AppState;
+use crate::theme;
+use crate::widgets::ThemeScope;
 use druid::widget::{Controller, Widget, WidgetExt};
-use druid::{theme, Env, Event, EventCtx, Selector};
+use druid::{Env, Event, EventCtx, Key, Selector};
 
-pub use create_profile::PROFILE_CREATED;
+use clipboard_toast::ClipboardToast;
+use window::WindowController;
+
+pub use clipboard_toast::{CLIPBOARD_TIMEOUT_SECONDS, COPY};
+pub use create_profile::{IMPORT_BUNDLE, PROFILE_CREATED};
 pub use main::PROFILE_UPDATED;
+pub use settings::{configure_env, EXPORT_BUNDLE, SAVE_SETTINGS};
+pub use shortcuts::shortcut_command;
+pub use window::SAVE_WINDOW;
 pub const SAVE_PROFILE: Selector<Profile> = Selector::new("app.save_profile");
+pub const EXPORT_CALENDAR: Selector<Profile> = Selector::new("app.export_calendar");
+
+/// Number of days before the expiration of a document from which on a
+/// warning is shown.
+pub const EXPIRY_WARNING_DAYS: Key<u64> = Key::new("app.expiry_warning_days");
 
 struct AppController;
 
@@ -53,11 +72,17 @@ where
     }
 }
 
-pub fn build_ui() -> impl Widget<AppState> {
-    AppState::matcher()
-        .create(create_profile::build())
-        .main(main::build())
-        .background(theme::BACKGROUND_DARK)
-        .expand()
-        .controller(AppController)
+pub fn build_ui(selected_theme: Theme) -> impl Widget<AppState> {
+    ThemeScope::new(
+        selected_theme,
+        ClipboardToast::new(
+            AppState::matcher()
+                .create(create_profile::build())
+                .main(main::build())
+                .background(theme::BACKGROUND)
+                .expand()
+                .controller(AppController),
+        ),
+    )
+    .controller(WindowController::default())
 }
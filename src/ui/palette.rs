@@ -0,0 +1,222 @@
+//! Search palette to find and copy any value of the profile. It is opened
+//! with Ctrl+K or Cmd+K, the arrow keys select a result and Enter copies it.
+
+use super::some_lens::SomeLens;
+use super::COPY;
+use crate::data::{search, SearchItem};
+use crate::i18n;
+use crate::state::{HomeState, PaletteState};
+use crate::theme;
+use crate::widgets::{AutoFocus, Overlay, SecretLabel, AUTOFOCUS};
+use druid::lens::Map;
+use druid::widget::{
+    Controller, CrossAxisAlignment, Flex, Label, LineBreaking, List, Painter, SizedBox, TextBox,
+    ViewSwitcher,
+};
+use druid::{
+    Data, Env, Event, EventCtx, KbKey, LensExt, RenderContext, Selector, Widget, WidgetExt,
+};
+use std::sync::Arc;
+
+/// Open the search palette.
+pub const OPEN_PALETTE: Selector<()> = Selector::new("app.open_palette");
+
+const COPY_RESULT: Selector<String> = Selector::new("app.palette.copy_result");
+
+const MAX_RESULTS: usize = 8;
+
+#[derive(Clone, Data)]
+struct PaletteResult {
+    item: SearchItem,
+    is_selected: bool,
+}
+
+/// The best matching items for the query of the open palette.
+fn results(state: &HomeState) -> Vec<SearchItem> {
+    match &state.palette {
+        Some(palette) => search(&state.profile.search_items(), &palette.query)
+            .into_iter()
+            .take(MAX_RESULTS)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Opens the palette and closes it, once a result is copied.
+struct PaletteController;
+
+impl<W> Controller<HomeState, W> for PaletteController
+where
+    W: Widget<HomeState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut HomeState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Command(cmd) if cmd.is(OPEN_PALETTE) => {
+                if data.palette.is_none() {
+                    data.palette = Some(PaletteState::default());
+                }
+                ctx.set_handled();
+            }
+            Event::Notification(not) if not.is(COPY_RESULT) => {
+                let value = not.get(COPY_RESULT).unwrap();
+
+                ctx.submit_command(COPY.with(value.clone()));
+                ctx.resign_focus();
+                data.palette = None;
+                ctx.set_handled();
+            }
+            _ => {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+}
+
+/// Keyboard navigation of the results, while the query has the focus.
+struct KeyboardController;
+
+impl<W> Controller<HomeState, W> for KeyboardController
+where
+    W: Widget<HomeState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut HomeState,
+        env: &Env,
+    ) {
+        if let Event::KeyDown(key) = event {
+            let results = results(data);
+            let palette = data.palette.as_mut().unwrap();
+
+            match &key.key {
+                KbKey::ArrowDown => {
+                    palette.selected = (palette.selected + 1).min(results.len().saturating_sub(1));
+                    ctx.set_handled();
+                }
+                KbKey::ArrowUp => {
+                    palette.selected = palette.selected.saturating_sub(1);
+                    ctx.set_handled();
+                }
+                KbKey::Enter => {
+                    if let Some(item) = results.get(palette.selected) {
+                        ctx.submit_notification(COPY_RESULT.with(item.value.clone()));
+                    }
+                    ctx.set_handled();
+                }
+                KbKey::Escape => {
+                    ctx.resign_focus();
+                    data.palette = None;
+                    ctx.set_handled();
+                }
+                _ => {}
+            }
+        }
+
+        if ctx.is_handled() {
+            return;
+        }
+
+        let query = data.palette.as_ref().map(|palette| palette.query.clone());
+        child.event(ctx, event, data, env);
+
+        // Select the best match again, whenever the query changes.
+        if let Some(palette) = data.palette.as_mut() {
+            if query.as_ref() != Some(&palette.query) {
+                palette.selected = 0;
+            }
+        }
+    }
+}
+
+/// Shows the search palette above `child`.
+pub fn build(child: impl Widget<HomeState> + 'static) -> impl Widget<HomeState> {
+    Overlay::new(
+        child,
+        ViewSwitcher::new(
+            |state: &HomeState, _env| state.palette.is_some(),
+            |is_open, _state, _env| match is_open {
+                true => Box::new(build_palette()),
+                false => Box::new(SizedBox::empty()),
+            },
+        ),
+        |state: &HomeState| state.palette.is_some(),
+    )
+    .controller(PaletteController)
+}
+
+fn build_palette() -> impl Widget<HomeState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(
+            TextBox::new()
+                .with_placeholder(i18n::text("palette-search"))
+                .controller(AutoFocus)
+                .env_scope(|env, _| env.set(AUTOFOCUS, true))
+                .expand_width()
+                .lens(HomeState::palette.then(SomeLens).then(PaletteState::query)),
+        )
+        .with_default_spacer()
+        .with_child(List::new(build_result).with_spacing(2.0).lens(Map::new(
+            |state: &HomeState| {
+                let selected = state.palette.as_ref().map_or(0, |palette| palette.selected);
+                let results: Vec<PaletteResult> = results(state)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, item)| PaletteResult {
+                        item,
+                        is_selected: index == selected,
+                    })
+                    .collect();
+
+                Arc::new(results)
+            },
+            |_state: &mut HomeState, _results: Arc<Vec<PaletteResult>>| {},
+        )))
+        .with_default_spacer()
+        .with_child(
+            Label::new(i18n::text("palette-hint"))
+                .with_text_color(theme::MUTED_TEXT)
+                .with_text_size(12.0),
+        )
+        .padding(10.0)
+        .background(theme::BACKGROUND)
+        .border(theme::BORDER, 1.0)
+        .rounded(4.0)
+        .fix_width(500.0)
+        .controller(KeyboardController)
+}
+
+fn build_result() -> impl Widget<PaletteResult> {
+    Flex::row()
+        .with_flex_child(
+            Label::dynamic(|result: &PaletteResult, _env| result.item.label.clone())
+                .with_line_break_mode(LineBreaking::Clip)
+                .expand_width(),
+            1.0,
+        )
+        .with_default_spacer()
+        .with_child(SecretLabel::new(
+            |result: &PaletteResult, _env| result.item.value.clone(),
+            |result: &PaletteResult, _env| result.item.is_secret,
+        ))
+        .padding((8.0, 4.0))
+        .background(Painter::new(|ctx, result: &PaletteResult, env| {
+            if result.is_selected {
+                let rect = ctx.size().to_rounded_rect(4.0);
+                ctx.fill(rect, &env.get(theme::SURFACE));
+            }
+        }))
+        .on_click(|ctx, result: &mut PaletteResult, _env| {
+            ctx.submit_notification(COPY_RESULT.with(result.item.value.clone()))
+        })
+}
@@ -0,0 +1,156 @@
+//! Lists of bank accounts and miscellaneous items, which can be reordered.
+//! A row is moved by dragging its handle or with Alt+↑ and Alt+↓, while the
+//! row or one of its buttons has the focus.
+
+use crate::data::{ItemId, Orderable};
+use crate::i18n;
+use crate::theme;
+use crate::widgets::OutlineButton;
+use druid::widget::{Controller, CrossAxisAlignment, Flex, Label, Painter};
+use druid::{
+    Cursor, Data, Env, Event, EventCtx, KbKey, LifeCycle, LifeCycleCtx, RenderContext, Selector,
+    Widget, WidgetExt,
+};
+
+/// Move the bank account or item by the given steps.
+pub const MOVE: Selector<(ItemId, isize)> = Selector::new("app.reorder.move");
+/// Pin or unpin the bank account or item.
+pub const TOGGLE_PIN: Selector<ItemId> = Selector::new("app.reorder.toggle_pin");
+
+/// The handle was pressed at the given vertical window position.
+const START_DRAG: Selector<f64> = Selector::new("app.reorder.start_drag");
+/// Focus the row of the moved item, as the rows keep their position.
+const FOCUS_ITEM: Selector<ItemId> = Selector::new("app.reorder.focus_item");
+
+struct Drag {
+    id: ItemId,
+    /// Vertical window position, at which the row was last moved.
+    y: f64,
+}
+
+/// Moves the row with the drag handle and the keyboard.
+struct Reorder {
+    drag: Option<Drag>,
+}
+
+impl<T, W> Controller<T, W> for Reorder
+where
+    T: Orderable + Data,
+    W: Widget<T>,
+{
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Notification(not) if not.is(START_DRAG) => {
+                self.drag = Some(Drag {
+                    id: data.id(),
+                    y: *not.get(START_DRAG).unwrap(),
+                });
+                ctx.set_active(true);
+                ctx.set_handled();
+            }
+            Event::MouseMove(mouse) if ctx.is_active() => {
+                if let Some(drag) = self.drag.as_mut() {
+                    let row_height = ctx.size().height;
+                    let steps = ((mouse.window_pos.y - drag.y) / row_height).trunc();
+
+                    if steps != 0.0 {
+                        ctx.submit_notification(MOVE.with((drag.id, steps as isize)));
+                        drag.y += steps * row_height;
+                    }
+                }
+                ctx.set_cursor(&Cursor::ResizeUpDown);
+                ctx.set_handled();
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                self.drag = None;
+                ctx.set_active(false);
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if key.mods.alt() => {
+                let steps = match key.key {
+                    KbKey::ArrowUp => -1,
+                    KbKey::ArrowDown => 1,
+                    _ => return child.event(ctx, event, data, env),
+                };
+
+                ctx.submit_notification(MOVE.with((data.id(), steps)));
+                ctx.submit_command(FOCUS_ITEM.with(data.id()));
+                ctx.set_handled();
+            }
+            Event::Command(cmd) if cmd.is(FOCUS_ITEM) => {
+                if *cmd.get_unchecked(FOCUS_ITEM) == data.id() {
+                    ctx.request_focus();
+                    ctx.set_handled();
+                }
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+    ) {
+        match event {
+            LifeCycle::WidgetAdded => ctx.register_for_focus(),
+            LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            _ => (),
+        }
+
+        child.lifecycle(ctx, event, data, env)
+    }
+}
+
+/// Starts dragging the row, when it is pressed.
+struct HandleController;
+
+impl<T, W: Widget<T>> Controller<T, W> for HandleController {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::MouseDown(mouse) => {
+                ctx.submit_notification(START_DRAG.with(mouse.window_pos.y));
+                ctx.set_handled();
+            }
+            Event::MouseMove(_) => ctx.set_cursor(&Cursor::ResizeUpDown),
+            _ => (),
+        }
+
+        child.event(ctx, event, data, env)
+    }
+}
+
+fn drag_handle<T: Data>() -> impl Widget<T> {
+    Label::new("⋮⋮")
+        .with_text_color(theme::MUTED_TEXT)
+        .controller(HandleController)
+}
+
+/// Row with a drag handle, for a list without spacing between the rows,
+/// e.g. a `VirtualList`. The pinned items come first.
+pub fn row<T: Orderable + Data>(content: impl Widget<T> + 'static) -> impl Widget<T> {
+    Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .with_spacer(10.0)
+        .with_child(drag_handle())
+        .with_flex_child(content, 1.0)
+        .background(Painter::new(|ctx, _data: &T, env| {
+            if ctx.is_focused() {
+                let rect = ctx.size().to_rect().inset(-1.0).to_rounded_rect(4.0);
+                ctx.stroke(rect, &env.get(theme::ACCENT), 2.0);
+            }
+        }))
+        .controller(Reorder { drag: None })
+}
+
+/// Button to pin the item to the top of the list or to unpin it.
+pub fn pin_button<T: Orderable + Data>() -> impl Widget<T> {
+    OutlineButton::new(|item: &T, _env: &Env| match item.is_pinned() {
+        true => i18n::text("unpin"),
+        false => i18n::text("pin"),
+    })
+    .on_click(|ctx, item: &mut T, _env| ctx.submit_notification(TOGGLE_PIN.with(item.id())))
+}
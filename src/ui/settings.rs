@@ -0,0 +1,310 @@
+//! Settings page. The settings are loaded in `main` and passed to the
+//! widgets through the `Env`, so changes are saved to `settings.json` and
+//! take effect after a restart. Only the theme is switched immediately.
+//!
+//! The page also exports the data directory as encrypted bundle, which is
+//! imported on the start screen of another machine.
+
+use super::form::{FormShortcuts, SUBMITTED};
+use super::{CLIPBOARD_TIMEOUT_SECONDS, EXPIRY_WARNING_DAYS};
+use crate::data::{
+    DataError, DateFormat, ErrorCode, ReminderConfig, Settings, Theme, MIN_PASSPHRASE_LENGTH,
+};
+use crate::i18n;
+use crate::theme::{self, Palette};
+use crate::widgets::{date_format, input, InputState, OutlineButton, DATE_FORMAT, SET_THEME};
+use druid::widget::{
+    Controller, CrossAxisAlignment, Flex, Label, LineBreaking, RadioGroup, Scroll, Widget,
+    WidgetExt,
+};
+use druid::{ArcStr, Data, Env, Event, EventCtx, Key, Lens, Selector, UpdateCtx};
+use std::sync::Arc;
+
+/// Save the settings to `settings.json`.
+pub const SAVE_SETTINGS: Selector<Settings> = Selector::new("app.save_settings");
+/// Choose where to save the bundle and export it with the given passphrase.
+pub const EXPORT_BUNDLE: Selector<String> = Selector::new("app.export_bundle");
+
+/// Directory of the profile, empty for the default directory.
+pub const DATA_DIR: Key<ArcStr> = Key::new("app.settings.data_dir");
+/// Language tag of the UI, empty for the language of the system.
+pub const LANGUAGE: Key<ArcStr> = Key::new("app.settings.language");
+/// Reminder lead times in days, e.g. `90, 30, 7`.
+pub const REMINDER_DAYS: Key<ArcStr> = Key::new("app.settings.reminder_days");
+
+/// Make the settings available to the widgets.
+pub fn configure_env(env: &mut Env, settings: &Settings) {
+    let warning_days = settings.reminders().warning_window().num_days() as u64;
+
+    env.set(DATA_DIR, settings.data_dir.as_str());
+    env.set(LANGUAGE, settings.language.as_str());
+    env.set(DATE_FORMAT, settings.date_format.id());
+    env.set(REMINDER_DAYS, format_days(&settings.reminder_days).as_str());
+    env.set(EXPIRY_WARNING_DAYS, warning_days);
+    env.set(CLIPBOARD_TIMEOUT_SECONDS, settings.clipboard_timeout);
+    env.set(theme::THEME, settings.theme.id());
+    Palette::of(theme::resolve(settings.theme)).apply(env);
+}
+
+fn format_days(days: &[u32]) -> String {
+    days.iter()
+        .map(|days| days.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn input_state(value: impl Into<String>) -> InputState {
+    InputState {
+        value: Arc::new(value.into()),
+        error: None,
+    }
+}
+
+#[derive(Clone, Data, Lens, PartialEq, Eq, Debug)]
+pub struct SettingsState {
+    data_dir: InputState,
+    language: String,
+    date_format: DateFormat,
+    theme: Theme,
+    reminder_days: InputState,
+    /// Seconds until copied values are cleared, `0` keeps them.
+    clipboard_timeout: InputState,
+    /// Whether the settings were saved, since the page was opened.
+    is_saved: bool,
+    /// Passphrase of the bundle to export.
+    passphrase: InputState,
+    is_exported: bool,
+}
+
+impl SettingsState {
+    /// The settings, which the app was started with.
+    pub fn from_env(env: &Env) -> SettingsState {
+        SettingsState {
+            data_dir: input_state(env.get(DATA_DIR).as_ref()),
+            language: env.get(LANGUAGE).to_string(),
+            date_format: date_format(env),
+            theme: theme::current(env),
+            reminder_days: input_state(env.get(REMINDER_DAYS).as_ref()),
+            clipboard_timeout: input_state(env.get(CLIPBOARD_TIMEOUT_SECONDS).to_string()),
+            is_saved: false,
+            passphrase: InputState::default(),
+            is_exported: false,
+        }
+    }
+
+    /// Show whether the bundle was saved or why not.
+    pub fn set_export_result(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                self.passphrase = InputState::default();
+                self.is_exported = true;
+            }
+            Err(error) => self.passphrase.set_error(error),
+        }
+    }
+
+    /// The passphrase for the bundle, if it is long enough.
+    fn export_passphrase(&mut self) -> Option<String> {
+        self.passphrase.reset_error();
+        self.is_exported = false;
+
+        if self.passphrase.value.chars().count() < MIN_PASSPHRASE_LENGTH {
+            self.passphrase.set_error(i18n::text_with(
+                "bundle-passphrase-too-short",
+                &[("length", MIN_PASSPHRASE_LENGTH.into())],
+            ));
+            return None;
+        }
+
+        Some(self.passphrase.value.to_string())
+    }
+
+    /// Validate the entered settings. Errors are shown on the page and
+    /// `None` is returned. The window is remembered by `main`.
+    fn submit(&mut self) -> Option<Settings> {
+        self.reminder_days.reset_error();
+        let reminders = match self.reminder_days.value.parse::<ReminderConfig>() {
+            Ok(reminders) => Some(reminders),
+            Err(error) => {
+                self.reminder_days.set_error(i18n::error(&error));
+                None
+            }
+        };
+
+        self.clipboard_timeout.reset_error();
+        let value = self.clipboard_timeout.value.trim();
+        let clipboard_timeout = match value.parse::<u64>() {
+            Ok(seconds) => Some(seconds),
+            Err(_) => {
+                let error = DataError::new(ErrorCode::InvalidNumber, value);
+                self.clipboard_timeout.set_error(i18n::error(&error));
+                None
+            }
+        };
+
+        let mut settings = Settings {
+            data_dir: String::from(self.data_dir.value.trim()),
+            language: self.language.clone(),
+            date_format: self.date_format,
+            theme: self.theme,
+            clipboard_timeout: clipboard_timeout?,
+            ..Settings::default()
+        };
+        settings.set_reminders(&reminders?);
+
+        Some(settings)
+    }
+}
+
+struct SettingsController;
+
+impl<W: Widget<SettingsState>> Controller<SettingsState, W> for SettingsController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut SettingsState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Notification(not) if not.is(SUBMITTED) => {
+                if let Some(settings) = data.submit() {
+                    ctx.submit_command(SAVE_SETTINGS.with(settings));
+                    data.is_saved = true;
+                }
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+
+    fn update(
+        &mut self,
+        child: &mut W,
+        ctx: &mut UpdateCtx,
+        old_data: &SettingsState,
+        data: &SettingsState,
+        env: &Env,
+    ) {
+        // Preview the theme, before it is saved.
+        if old_data.theme != data.theme {
+            ctx.submit_command(SET_THEME.with(data.theme));
+        }
+        child.update(ctx, old_data, data, env);
+    }
+}
+
+fn build_export() -> impl Widget<SettingsState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Label::new(i18n::text("settings-export")).with_text_size(20.0))
+        .with_default_spacer()
+        .with_child(
+            Label::new(i18n::text("settings-export-hint"))
+                .with_line_break_mode(LineBreaking::WordWrap)
+                .with_text_color(theme::MUTED_TEXT)
+                .with_text_size(12.0),
+        )
+        .with_default_spacer()
+        .with_child(input(&i18n::text("bundle-passphrase")).lens(SettingsState::passphrase))
+        .with_default_spacer()
+        .with_child(
+            OutlineButton::new(i18n::text("settings-export-button"))
+                .disabled_if(|state: &SettingsState, _env| state.passphrase.value.is_empty())
+                .on_click(|ctx, state: &mut SettingsState, _env| {
+                    if let Some(passphrase) = state.export_passphrase() {
+                        ctx.submit_command(EXPORT_BUNDLE.with(passphrase));
+                    }
+                }),
+        )
+        .with_default_spacer()
+        .with_child(
+            Label::dynamic(|state: &SettingsState, _env| match state.is_exported {
+                true => i18n::text("settings-exported"),
+                false => String::new(),
+            })
+            .with_text_color(theme::MUTED_TEXT),
+        )
+}
+
+fn section_label(id: &str) -> impl Widget<SettingsState> {
+    Label::new(i18n::text(id)).padding((0.0, 0.0, 0.0, 5.0))
+}
+
+pub fn build() -> impl Widget<SettingsState> {
+    let settings = Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Label::new(i18n::text("settings")).with_text_size(20.0))
+        .with_spacer(20.0)
+        .with_child(input(&i18n::text("settings-data-dir")).lens(SettingsState::data_dir))
+        .with_child(
+            Label::new(i18n::text("settings-data-dir-hint"))
+                .with_text_color(theme::MUTED_TEXT)
+                .with_text_size(12.0),
+        )
+        .with_spacer(20.0)
+        .with_child(section_label("settings-language"))
+        .with_child(
+            RadioGroup::new(vec![
+                (i18n::text("settings-language-system"), String::new()),
+                (String::from("Deutsch"), String::from("de")),
+                (String::from("English"), String::from("en")),
+            ])
+            .lens(SettingsState::language),
+        )
+        .with_spacer(20.0)
+        .with_child(section_label("settings-date-format"))
+        .with_child(
+            RadioGroup::new(vec![
+                ("01.02.2030", DateFormat::German),
+                ("2030-02-01", DateFormat::Iso),
+            ])
+            .lens(SettingsState::date_format),
+        )
+        .with_spacer(20.0)
+        .with_child(section_label("settings-theme"))
+        .with_child(
+            RadioGroup::new(vec![
+                (i18n::text("settings-theme-system"), Theme::System),
+                (i18n::text("settings-theme-dark"), Theme::Dark),
+                (i18n::text("settings-theme-light"), Theme::Light),
+                (
+                    i18n::text("settings-theme-high-contrast"),
+                    Theme::HighContrast,
+                ),
+            ])
+            .lens(SettingsState::theme),
+        )
+        .with_spacer(20.0)
+        .with_child(input(&i18n::text("settings-reminder-days")).lens(SettingsState::reminder_days))
+        .with_spacer(20.0)
+        .with_child(
+            input(&i18n::text("settings-clipboard-timeout")).lens(SettingsState::clipboard_timeout),
+        )
+        .with_spacer(20.0)
+        .with_child(
+            OutlineButton::new(i18n::text("save"))
+                .primary()
+                .on_click(|ctx, _state, _env| ctx.submit_notification(SUBMITTED)),
+        )
+        .with_default_spacer()
+        .with_child(
+            Label::dynamic(|state: &SettingsState, _env| match state.is_saved {
+                true => i18n::text("settings-saved"),
+                false => String::new(),
+            })
+            .with_line_break_mode(LineBreaking::WordWrap)
+            .with_text_color(theme::MUTED_TEXT),
+        )
+        .controller(FormShortcuts);
+
+    let page = Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(settings)
+        .with_spacer(40.0)
+        .with_child(build_export())
+        .padding(20.0);
+
+    Scroll::new(page).vertical().controller(SettingsController)
+}
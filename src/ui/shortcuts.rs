@@ -0,0 +1,66 @@
+//! Global keyboard shortcuts. Key events only reach the focused widget, so
+//! the app delegate turns these shortcuts into commands for the window.
+
+use super::main::{CREATE_ON_PAGE, NAVIGATE};
+use super::palette::OPEN_PALETTE;
+use crate::state::Nav;
+use druid::{Command, HotKey, KeyEvent, SysMods};
+
+/// The command for `key`, if it is a global shortcut.
+pub fn shortcut_command(key: &KeyEvent) -> Option<Command> {
+    let pages = [
+        ("1", Nav::Home),
+        ("2", Nav::BankAccounts),
+        ("3", Nav::Miscellaneous),
+        ("4", Nav::History),
+        ("5", Nav::Settings),
+    ];
+
+    if HotKey::new(SysMods::Cmd, "k").matches(key) {
+        return Some(OPEN_PALETTE.into());
+    }
+    if HotKey::new(SysMods::Cmd, "n").matches(key) {
+        return Some(CREATE_ON_PAGE.into());
+    }
+
+    pages
+        .iter()
+        .find(|(digit, _)| HotKey::new(SysMods::Cmd, *digit).matches(key))
+        .map(|(_, nav)| NAVIGATE.with(*nav))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use druid::{Modifiers, RawMods};
+
+    fn key(mods: impl Into<Modifiers>, key: &str) -> KeyEvent {
+        KeyEvent::for_test(mods, key)
+    }
+
+    #[test]
+    fn should_navigate_with_the_number_keys() {
+        let command = shortcut_command(&key(RawMods::from(SysMods::Cmd), "2")).unwrap();
+        let history = shortcut_command(&key(RawMods::from(SysMods::Cmd), "4")).unwrap();
+        let settings = shortcut_command(&key(RawMods::from(SysMods::Cmd), "5")).unwrap();
+
+        assert_eq!(command.get(NAVIGATE), Some(&Nav::BankAccounts));
+        assert_eq!(history.get(NAVIGATE), Some(&Nav::History));
+        assert_eq!(settings.get(NAVIGATE), Some(&Nav::Settings));
+    }
+
+    #[test]
+    fn should_open_the_palette_and_create_on_the_page() {
+        let palette = shortcut_command(&key(RawMods::from(SysMods::Cmd), "k")).unwrap();
+        let create = shortcut_command(&key(RawMods::from(SysMods::Cmd), "n")).unwrap();
+
+        assert!(palette.is(OPEN_PALETTE));
+        assert!(create.is(CREATE_ON_PAGE));
+    }
+
+    #[test]
+    fn should_ignore_keys_without_modifier() {
+        assert!(shortcut_command(&key(RawMods::None, "2")).is_none());
+        assert!(shortcut_command(&key(RawMods::None, "k")).is_none());
+    }
+}
@@ -0,0 +1,263 @@
+//! Editor for the templates of structured miscellaneous entries.
+//!
+//! Like the forms in `form`, the editor only emits the `SUBMITTED` and
+//! `CANCELED` notifications and the surrounding controller saves the
+//! template by calling `FormState::submit`.
+
+use super::form::{centered, FormShortcuts, CANCELED, SUBMITTED};
+use crate::data::{
+    next_id, used_field_ids, Entry, ItemId, Template, TemplateField, TemplateFieldKind,
+};
+use crate::i18n;
+use crate::state::ProfileState;
+use crate::theme;
+use crate::widgets::{input, Card, InputState, OutlineButton, AUTOFOCUS};
+use druid::im::Vector;
+use druid::widget::{
+    Controller, CrossAxisAlignment, Flex, Label, List, MainAxisAlignment, Maybe, Widget, WidgetExt,
+};
+use druid::{Data, Env, Event, EventCtx, Lens, Selector};
+use std::sync::Arc;
+
+const REMOVE_FIELD: Selector<u64> = Selector::new("app.main.template_editor.remove_field");
+
+#[derive(Data, Lens, PartialEq, Eq, Clone, Debug)]
+pub struct FieldState {
+    id: u64,
+    label: InputState,
+    kind: TemplateFieldKind,
+}
+
+#[derive(Data, Lens, PartialEq, Eq, Clone, Default, Debug)]
+pub struct FormState {
+    template_id: Option<ItemId>,
+    name: InputState,
+    fields: Vector<FieldState>,
+    /// Field ids, which the entries of the template reference, also of
+    /// removed fields.
+    used_field_ids: Vector<u64>,
+    error: Option<String>,
+}
+
+impl FormState {
+    pub fn new<'a>(
+        template: Option<&Template>,
+        entries: impl Iterator<Item = &'a Entry>,
+    ) -> FormState {
+        match template {
+            Some(template) => FormState {
+                template_id: Some(template.id),
+                name: InputState {
+                    value: Arc::new(template.name.clone()),
+                    error: None,
+                },
+                fields: template
+                    .fields
+                    .iter()
+                    .map(|field| FieldState {
+                        id: field.id,
+                        label: InputState {
+                            value: Arc::new(field.label.clone()),
+                            error: None,
+                        },
+                        kind: field.kind,
+                    })
+                    .collect(),
+                used_field_ids: used_field_ids(template.id, entries).into(),
+                error: None,
+            },
+            None => {
+                let mut state = FormState::default();
+                state.add_field();
+                state
+            }
+        }
+    }
+
+    fn add_field(&mut self) {
+        self.fields.push_back(FieldState {
+            id: next_id(
+                self.fields
+                    .iter()
+                    .map(|field| field.id)
+                    .chain(self.used_field_ids.iter().copied()),
+            ),
+            label: InputState::default(),
+            kind: TemplateFieldKind::Text,
+        });
+    }
+
+    fn remove_field(&mut self, id: u64) {
+        self.fields.retain(|field| field.id != id);
+    }
+
+    /// Validate the template and, if it is valid, add it to `profile` or
+    /// replace the edited template. Entries of an edited template are
+    /// updated to its new fields.
+    pub fn submit(&mut self, profile: &mut ProfileState) -> bool {
+        let mut has_error = false;
+
+        self.name.reset_error();
+        if self.name.value.trim().is_empty() {
+            self.name.set_error(i18n::text("error-required"));
+            has_error = true;
+        }
+
+        for field in self.fields.iter_mut() {
+            field.label.reset_error();
+            if field.label.value.trim().is_empty() {
+                field.label.set_error(i18n::text("error-required"));
+                has_error = true;
+            }
+        }
+
+        self.error = match self.fields.is_empty() {
+            true => Some(i18n::text("template-no-fields")),
+            false => None,
+        };
+        if has_error || self.error.is_some() {
+            return false;
+        }
+
+        let template = Template {
+            id: self.template_id.unwrap_or_else(ItemId::new),
+            name: String::from(self.name.value.trim()),
+            fields: self
+                .fields
+                .iter()
+                .map(|field| TemplateField {
+                    id: field.id,
+                    label: String::from(field.label.value.trim()),
+                    kind: field.kind,
+                })
+                .collect(),
+        };
+
+        match profile
+            .templates
+            .iter()
+            .position(|existing| existing.id == template.id)
+        {
+            Some(index) => {
+                profile.templates.set(index, template.clone());
+            }
+            None => profile.templates.push_back(template.clone()),
+        }
+
+        for entry in profile.entries.iter_mut() {
+            if entry.template_id == template.id {
+                entry.apply_template(&template);
+            }
+        }
+
+        true
+    }
+}
+
+struct FieldListController;
+
+impl<W> Controller<FormState, W> for FieldListController
+where
+    W: Widget<FormState>,
+{
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut FormState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Notification(not) if not.is(REMOVE_FIELD) => {
+                data.remove_field(*not.get(REMOVE_FIELD).unwrap());
+                ctx.set_handled();
+            }
+            _ => {
+                child.event(ctx, event, data, env);
+            }
+        }
+    }
+}
+
+pub fn build() -> impl Widget<FormState> {
+    centered(form())
+}
+
+fn form() -> impl Widget<FormState> {
+    Card::new(
+        Flex::column()
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .main_axis_alignment(MainAxisAlignment::Start)
+            .with_child(
+                Label::dynamic(|state: &FormState, _env| match state.template_id {
+                    Some(_) => i18n::text("template-edit"),
+                    None => i18n::text("template-create"),
+                })
+                .with_text_size(20.0),
+            )
+            .with_spacer(20.0)
+            .with_child(
+                input(&i18n::text("field-name"))
+                    .env_scope(|env, _| env.set(AUTOFOCUS, true))
+                    .lens(FormState::name),
+            )
+            .with_spacer(20.0)
+            .with_child(
+                List::new(build_field)
+                    .with_spacing(10.0)
+                    .lens(FormState::fields),
+            )
+            .with_default_spacer()
+            .with_child(
+                OutlineButton::new(i18n::text("template-add-field"))
+                    .on_click(|_ctx, state: &mut FormState, _env| state.add_field()),
+            )
+            .with_child(
+                Maybe::or_empty(|| {
+                    Label::dynamic(|error: &String, _| error.to_string())
+                        .with_text_color(theme::MUTED_TEXT)
+                        .with_text_size(12.0)
+                        .padding((0.0, 5.0, 0.0, 0.0))
+                })
+                .lens(FormState::error),
+            )
+            .with_spacer(20.0)
+            .with_child(
+                Flex::row()
+                    .with_child(
+                        OutlineButton::new(i18n::text("save"))
+                            .primary()
+                            .on_click(|ctx, _state, _env| ctx.submit_notification(SUBMITTED)),
+                    )
+                    .with_default_spacer()
+                    .with_child(
+                        OutlineButton::new(i18n::text("cancel"))
+                            .on_click(|ctx, _state, _env| ctx.submit_notification(CANCELED)),
+                    ),
+            )
+            .controller(FieldListController)
+            .controller(FormShortcuts),
+    )
+    .fix_width(500.0)
+}
+
+fn build_field() -> impl Widget<FieldState> {
+    Flex::row()
+        .cross_axis_alignment(CrossAxisAlignment::End)
+        .with_flex_child(
+            input(&i18n::text("field-label")).lens(FieldState::label),
+            1.0,
+        )
+        .with_default_spacer()
+        .with_child(
+            OutlineButton::new(|field: &FieldState, _env: &Env| i18n::text(field.kind.name()))
+                .on_click(|_ctx, field: &mut FieldState, _env| field.kind = field.kind.next()),
+        )
+        .with_default_spacer()
+        .with_child(OutlineButton::new(i18n::text("remove")).danger().on_click(
+            |ctx, field: &mut FieldState, _env| {
+                ctx.submit_notification(REMOVE_FIELD.with(field.id))
+            },
+        ))
+}
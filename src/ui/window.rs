@@ -0,0 +1,57 @@
+//! Remembers the size and position of the window for the next start.
+
+use druid::widget::{Controller, Widget};
+use druid::{Env, Event, EventCtx, Point, Selector, Size, TimerToken};
+use std::time::Duration;
+
+/// Save the size and position of the window to `settings.json`.
+pub const SAVE_WINDOW: Selector<(Size, Point)> = Selector::new("app.save_window");
+
+/// Moving a window sends no event, so the position is checked regularly.
+/// This also limits the saves, while the window is resized.
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Has to be the controller of the root widget, which is the only one
+/// receiving `Event::WindowSize`.
+pub struct WindowController {
+    size: Size,
+    saved: Option<(Size, Point)>,
+    timer: TimerToken,
+}
+
+impl Default for WindowController {
+    fn default() -> WindowController {
+        WindowController {
+            size: Size::ZERO,
+            saved: None,
+            timer: TimerToken::INVALID,
+        }
+    }
+}
+
+impl<T, W: Widget<T>> Controller<T, W> for WindowController {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::WindowConnected => {
+                self.timer = ctx.request_timer(CHECK_INTERVAL);
+                child.event(ctx, event, data, env);
+            }
+            Event::WindowSize(size) => {
+                self.size = *size;
+                child.event(ctx, event, data, env);
+            }
+            Event::Timer(token) if *token == self.timer => {
+                let geometry = (self.size, ctx.window().get_position());
+
+                // The first check only records the window, as it was opened.
+                if self.saved.map_or(false, |saved| saved != geometry) {
+                    ctx.submit_command(SAVE_WINDOW.with(geometry));
+                }
+                self.saved = Some(geometry);
+                self.timer = ctx.request_timer(CHECK_INTERVAL);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! Watches the profile for changes of other programs, e.g. of a client,
+//! which syncs the data directory between machines.
+
+use druid::{ExtEventSink, Selector, Target};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// The profile file was modified, possibly by the app itself.
+pub const PROFILE_FILE_CHANGED: Selector = Selector::new("app.profile_file_changed");
+
+/// The modification time is polled, because file system events are not
+/// reliable for synced and network directories.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Send `PROFILE_FILE_CHANGED` to the app, whenever the file at `path` is
+/// modified, created or removed.
+pub fn watch_profile(path: PathBuf, sink: ExtEventSink) {
+    thread::spawn(move || {
+        let mut modified = modified(&path);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let current = modified(&path);
+            if current != modified {
+                modified = current;
+
+                // Fails once the app was closed.
+                if sink
+                    .submit_command(PROFILE_FILE_CHANGED, (), Target::Auto)
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    });
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
@@ -0,0 +1,42 @@
+use druid::widget::prelude::*;
+use druid::widget::Controller;
+use druid::{Key, Selector};
+
+/// Whether widgets with the `AutoFocus` controller request the focus, once
+/// they are added. Set it for the first input of a form with `env_scope`.
+pub const AUTOFOCUS: Key<bool> = Key::new("app.autofocus");
+
+const REQUEST_FOCUS: Selector<()> = Selector::new("app.auto_focus.request_focus");
+
+/// Requests the focus for a focusable widget like a `TextBox`, as soon as it
+/// is added and `AUTOFOCUS` is set.
+pub struct AutoFocus;
+
+impl<T, W: Widget<T>> Controller<T, W> for AutoFocus {
+    fn event(&mut self, child: &mut W, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(REQUEST_FOCUS) => {
+                ctx.request_focus();
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &T,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            if env.try_get(AUTOFOCUS).unwrap_or(false) {
+                // The focus can only be requested while handling an event.
+                ctx.submit_command(REQUEST_FOCUS.to(ctx.widget_id()));
+            }
+        }
+        child.lifecycle(ctx, event, data, env);
+    }
+}
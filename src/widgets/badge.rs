@@ -0,0 +1,21 @@
+use crate::theme;
+use druid::widget::{Label, Painter};
+use druid::{Color, Data, Env, RenderContext, Widget, WidgetExt};
+
+const BADGE_RADIUS: f64 = 8.0;
+
+/// Small label on a colored, rounded background, e.g. to show the status
+/// of an item.
+pub fn badge<T: Data>(
+    text: impl Fn(&T, &Env) -> String + 'static,
+    color: impl Fn(&T, &Env) -> Color + 'static,
+) -> impl Widget<T> {
+    Label::dynamic(text)
+        .with_text_size(12.0)
+        .with_text_color(theme::ON_STATUS)
+        .padding((8.0, 2.0))
+        .background(Painter::new(move |ctx, data: &T, env| {
+            let rect = ctx.size().to_rounded_rect(BADGE_RADIUS);
+            ctx.fill(rect, &color(data, env));
+        }))
+}
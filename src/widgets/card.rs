@@ -1,6 +1,7 @@
+use crate::theme;
 use druid::widget::prelude::*;
 use druid::widget::Container;
-use druid::{theme, Data, Widget, WidgetExt};
+use druid::{Data, Widget, WidgetExt};
 
 pub struct Card<T> {
     child: Container<T>,
@@ -8,10 +9,7 @@ pub struct Card<T> {
 
 impl<T: Data> Card<T> {
     pub fn new(child: impl Widget<T> + 'static) -> Card<T> {
-        let container = child
-            .padding(40.0)
-            .border(theme::BORDER_LIGHT, 1.0)
-            .rounded(4.0);
+        let container = child.padding(40.0).border(theme::BORDER, 1.0).rounded(4.0);
 
         Card { child: container }
     }
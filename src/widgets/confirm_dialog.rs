@@ -0,0 +1,89 @@
+use super::{AutoFocus, OutlineButton, AUTOFOCUS};
+use crate::i18n;
+use crate::theme;
+use druid::widget::prelude::*;
+use druid::widget::{Checkbox, Controller, CrossAxisAlignment, Flex, Label, LineBreaking};
+use druid::{Data, KbKey, Lens, Selector, WidgetExt};
+
+/// The action was confirmed.
+pub const CONFIRMED: Selector<()> = Selector::new("app.confirm_dialog.confirmed");
+/// The dialog was closed without confirming the action.
+pub const DISMISSED: Selector<()> = Selector::new("app.confirm_dialog.dismissed");
+
+#[derive(Data, Lens, PartialEq, Eq, Clone, Debug)]
+pub struct ConfirmState {
+    pub message: String,
+    /// Whether the user does not want to be asked again.
+    pub dont_ask_again: bool,
+}
+
+impl ConfirmState {
+    pub fn new(message: impl Into<String>) -> ConfirmState {
+        ConfirmState {
+            message: message.into(),
+            dont_ask_again: false,
+        }
+    }
+}
+
+/// Closes the dialog with Escape.
+struct DialogController;
+
+impl<W: Widget<ConfirmState>> Controller<ConfirmState, W> for DialogController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut ConfirmState,
+        env: &Env,
+    ) {
+        match event {
+            Event::KeyDown(key) if key.key == KbKey::Escape => {
+                ctx.submit_notification(DISMISSED);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
+/// Dialog, which asks to confirm an action like deleting a document. It
+/// emits `CONFIRMED` or `DISMISSED`, the surrounding controller closes the
+/// dialog. Cancel is focused, so Enter does not confirm by accident.
+pub fn confirm_dialog(title: &str, confirm_label: &str) -> impl Widget<ConfirmState> {
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Label::new(title).with_text_size(20.0))
+        .with_spacer(20.0)
+        .with_child(
+            Label::dynamic(|state: &ConfirmState, _env| state.message.clone())
+                .with_line_break_mode(LineBreaking::WordWrap),
+        )
+        .with_spacer(20.0)
+        .with_child(
+            Checkbox::new(i18n::text("confirm-dont-ask-again")).lens(ConfirmState::dont_ask_again),
+        )
+        .with_spacer(20.0)
+        .with_child(
+            Flex::row()
+                .with_child(
+                    OutlineButton::new(confirm_label)
+                        .danger()
+                        .on_click(|ctx, _state, _env| ctx.submit_notification(CONFIRMED)),
+                )
+                .with_default_spacer()
+                .with_child(
+                    OutlineButton::new(i18n::text("cancel"))
+                        .on_click(|ctx, _state, _env| ctx.submit_notification(DISMISSED))
+                        .controller(AutoFocus)
+                        .env_scope(|env, _| env.set(AUTOFOCUS, true)),
+                ),
+        )
+        .controller(DialogController)
+        .padding(20.0)
+        .background(theme::BACKGROUND)
+        .border(theme::BORDER, 1.0)
+        .rounded(4.0)
+        .fix_width(400.0)
+}
@@ -0,0 +1,445 @@
+use super::input::error_label;
+use super::{AutoFocus, InputState, OutlineButton};
+use crate::data::{today, DateFormat};
+use crate::i18n;
+use crate::theme;
+use chrono::{Datelike, Duration, NaiveDate};
+use druid::piet::{FontFamily, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::widget::{Controller, CrossAxisAlignment, Flex, Label, TextBox};
+use druid::{ArcStr, Color, KbKey, Key, Point, Rect, Selector, WidgetExt, WidgetId};
+use std::fmt;
+use std::sync::Arc;
+
+/// Format of the shown dates, the id of a `DateFormat`.
+pub const DATE_FORMAT: Key<ArcStr> = Key::new("app.date_format");
+
+const TOGGLE_CALENDAR: Selector<()> = Selector::new("widgets.date_input.toggle_calendar");
+
+const CELL_SIZE: f64 = 32.0;
+const HEADER_HEIGHT: f64 = 32.0;
+const FONT_SIZE: f64 = 13.0;
+
+/// Message ids of the month and weekday names.
+const MONTH_NAMES: [&str; 12] = [
+    "month-january",
+    "month-february",
+    "month-march",
+    "month-april",
+    "month-may",
+    "month-june",
+    "month-july",
+    "month-august",
+    "month-september",
+    "month-october",
+    "month-november",
+    "month-december",
+];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "weekday-monday",
+    "weekday-tuesday",
+    "weekday-wednesday",
+    "weekday-thursday",
+    "weekday-friday",
+    "weekday-saturday",
+    "weekday-sunday",
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DateError {
+    Empty,
+    InvalidFormat,
+    InvalidDate,
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let id = match self {
+            DateError::Empty => "date-error-empty",
+            DateError::InvalidFormat => "date-error-invalid-format",
+            DateError::InvalidDate => "date-error-invalid-date",
+        };
+
+        write!(f, "{}", i18n::text(id))
+    }
+}
+
+/// Parse a date in the German format `1.2.2030` or `01.02.30` or in the
+/// ISO format `2030-02-01`. Two digit years are interpreted as `20xx`.
+pub fn parse_date(value: &str) -> Result<NaiveDate, DateError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(DateError::Empty);
+    }
+
+    let parts: Vec<&str> = if value.contains('-') {
+        value.split('-').collect()
+    } else {
+        let mut parts: Vec<&str> = value.trim_end_matches('.').split('.').collect();
+        parts.reverse();
+        parts
+    };
+
+    if parts.len() != 3 || parts.iter().any(|part| part.is_empty()) {
+        return Err(DateError::InvalidFormat);
+    }
+
+    let year = parse_year(parts[0])?;
+    let month = parse_number(parts[1])?;
+    let day = parse_number(parts[2])?;
+
+    NaiveDate::from_ymd_opt(year, month, day).ok_or(DateError::InvalidDate)
+}
+
+/// The configured `DATE_FORMAT`, the German format by default.
+pub fn date_format(env: &Env) -> DateFormat {
+    env.try_get(DATE_FORMAT)
+        .ok()
+        .and_then(|id| DateFormat::from_id(&id))
+        .unwrap_or(DateFormat::German)
+}
+
+pub fn format_date(date: NaiveDate, env: &Env) -> String {
+    date_format(env).format(date)
+}
+
+fn parse_year(value: &str) -> Result<i32, DateError> {
+    let year = parse_number(value)? as i32;
+
+    match value.len() {
+        2 => Ok(2000 + year),
+        4 => Ok(year),
+        _ => Err(DateError::InvalidFormat),
+    }
+}
+
+fn parse_number(value: &str) -> Result<u32, DateError> {
+    if value.len() > 4 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return Err(DateError::InvalidFormat);
+    }
+
+    value.parse().map_err(|_| DateError::InvalidFormat)
+}
+
+/// Text input for dates with a calendar, which can be opened with the
+/// `Kalender` button or `Alt+↓`.
+pub fn date_input(title: &str) -> impl Widget<InputState> {
+    let calendar_id = WidgetId::next();
+
+    Flex::column()
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .with_child(Label::new(title))
+        .with_child(
+            Flex::row()
+                .with_flex_child(
+                    TextBox::new()
+                        .with_placeholder(i18n::text("date-placeholder"))
+                        .controller(AutoFocus)
+                        .lens(InputState::value)
+                        .expand_width(),
+                    1.0,
+                )
+                .with_spacer(5.0)
+                .with_child(
+                    OutlineButton::new(i18n::text("date-calendar")).on_click(move |ctx, _, _| {
+                        ctx.submit_command(TOGGLE_CALENDAR.to(calendar_id))
+                    }),
+                )
+                .padding((0.0, 5.0, 0.0, 0.0)),
+        )
+        .with_child(Calendar::new().with_id(calendar_id))
+        .with_child(error_label())
+        .controller(DateInputController { calendar_id })
+}
+
+struct DateInputController {
+    calendar_id: WidgetId,
+}
+
+impl<W: Widget<InputState>> Controller<InputState, W> for DateInputController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut InputState,
+        env: &Env,
+    ) {
+        match event {
+            Event::KeyDown(key) if key.key == KbKey::ArrowDown && key.mods.alt() => {
+                ctx.submit_command(TOGGLE_CALENDAR.to(self.calendar_id));
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
+/// Month view below the text input. Selecting a day writes the date into
+/// the input.
+///
+/// Keyboard navigation: arrow keys move by day or week, page up/down by
+/// month, `Enter` selects the date and `Escape` closes the calendar.
+struct Calendar {
+    is_open: bool,
+    cursor: NaiveDate,
+}
+
+impl Calendar {
+    fn new() -> Calendar {
+        Calendar {
+            is_open: false,
+            cursor: today(),
+        }
+    }
+
+    fn open(&mut self, ctx: &mut EventCtx, data: &InputState) {
+        self.is_open = true;
+        self.cursor = parse_date(&data.value).unwrap_or_else(|_| today());
+        ctx.children_changed();
+        ctx.request_layout();
+        ctx.request_focus();
+    }
+
+    fn close(&mut self, ctx: &mut EventCtx) {
+        self.is_open = false;
+        ctx.children_changed();
+        ctx.request_layout();
+        ctx.resign_focus();
+    }
+
+    fn select(&mut self, ctx: &mut EventCtx, data: &mut InputState, env: &Env) {
+        data.value = Arc::new(format_date(self.cursor, env));
+        data.reset_error();
+        self.close(ctx);
+    }
+
+    fn first_visible_day(&self) -> NaiveDate {
+        let first_of_month = self.cursor.with_day(1).unwrap();
+        first_of_month - Duration::days(first_of_month.weekday().num_days_from_monday().into())
+    }
+
+    fn day_at(&self, pos: Point) -> Option<NaiveDate> {
+        if pos.y < 2.0 * HEADER_HEIGHT || pos.x < 0.0 || pos.x >= 7.0 * CELL_SIZE {
+            return None;
+        }
+
+        let column = (pos.x / CELL_SIZE) as i64;
+        let row = ((pos.y - 2.0 * HEADER_HEIGHT) / CELL_SIZE) as i64;
+        if row >= 6 {
+            return None;
+        }
+
+        Some(self.first_visible_day() + Duration::days(row * 7 + column))
+    }
+}
+
+impl Widget<InputState> for Calendar {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut InputState, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(TOGGLE_CALENDAR) => {
+                if self.is_open {
+                    self.close(ctx);
+                } else {
+                    self.open(ctx, data);
+                }
+                ctx.set_handled();
+            }
+            Event::MouseDown(mouse) if self.is_open => {
+                if mouse.pos.y < HEADER_HEIGHT {
+                    let months = if mouse.pos.x < CELL_SIZE {
+                        -1
+                    } else if mouse.pos.x > 6.0 * CELL_SIZE {
+                        1
+                    } else {
+                        0
+                    };
+                    self.cursor = add_months(self.cursor, months);
+                    ctx.request_paint();
+                } else if let Some(day) = self.day_at(mouse.pos) {
+                    self.cursor = day;
+                    self.select(ctx, data, env);
+                }
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if self.is_open => {
+                match &key.key {
+                    KbKey::ArrowLeft => self.cursor = self.cursor - Duration::days(1),
+                    KbKey::ArrowRight => self.cursor = self.cursor + Duration::days(1),
+                    KbKey::ArrowUp => self.cursor = self.cursor - Duration::days(7),
+                    KbKey::ArrowDown => self.cursor = self.cursor + Duration::days(7),
+                    KbKey::PageUp => self.cursor = add_months(self.cursor, -1),
+                    KbKey::PageDown => self.cursor = add_months(self.cursor, 1),
+                    KbKey::Enter => self.select(ctx, data, env),
+                    KbKey::Escape => self.close(ctx),
+                    _ => return,
+                }
+                ctx.request_paint();
+                ctx.set_handled();
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        _data: &InputState,
+        _env: &Env,
+    ) {
+        if let LifeCycle::BuildFocusChain = event {
+            if self.is_open {
+                ctx.register_for_focus();
+            }
+        }
+    }
+
+    fn update(
+        &mut self,
+        _ctx: &mut UpdateCtx,
+        _old_data: &InputState,
+        _data: &InputState,
+        _env: &Env,
+    ) {
+    }
+
+    fn layout(
+        &mut self,
+        _ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        _data: &InputState,
+        _env: &Env,
+    ) -> Size {
+        if self.is_open {
+            bc.constrain(Size::new(
+                7.0 * CELL_SIZE,
+                2.0 * HEADER_HEIGHT + 6.0 * CELL_SIZE,
+            ))
+        } else {
+            bc.constrain(Size::ZERO)
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &InputState, env: &Env) {
+        if !self.is_open {
+            return;
+        }
+
+        let text_color = env.get(theme::TEXT);
+        let muted_color = env.get(theme::MUTED_TEXT);
+        let highlight_color = env.get(theme::ACCENT);
+
+        let header = Rect::new(0.0, 0.0, 7.0 * CELL_SIZE, HEADER_HEIGHT);
+        let title = format!(
+            "{} {}",
+            i18n::text(MONTH_NAMES[self.cursor.month0() as usize]),
+            self.cursor.year()
+        );
+        draw_centered_text(ctx, &title, header, text_color);
+        draw_centered_text(ctx, "‹", cell_rect(0, 0.0), text_color);
+        draw_centered_text(ctx, "›", cell_rect(6, 0.0), text_color);
+
+        for (column, name) in WEEKDAY_NAMES.iter().enumerate() {
+            let name = i18n::text(name);
+            draw_centered_text(ctx, &name, cell_rect(column, HEADER_HEIGHT), muted_color);
+        }
+
+        let selected = parse_date(&data.value).ok();
+        let first_visible_day = self.first_visible_day();
+
+        for index in 0..42 {
+            let day = first_visible_day + Duration::days(index);
+            let rect = cell_rect(
+                (index % 7) as usize,
+                2.0 * HEADER_HEIGHT + (index / 7) as f64 * CELL_SIZE,
+            );
+            let rounded_rect = rect.inset(-2.0).to_rounded_rect(4.0);
+
+            let color = if day == self.cursor {
+                ctx.fill(rounded_rect, &highlight_color);
+                env.get(theme::BACKGROUND)
+            } else if day.month() != self.cursor.month() {
+                muted_color
+            } else {
+                text_color
+            };
+
+            if Some(day) == selected || day == today() {
+                ctx.stroke(rounded_rect, &highlight_color, 1.0);
+            }
+
+            draw_centered_text(ctx, &day.day().to_string(), rect, color);
+        }
+    }
+}
+
+fn draw_centered_text(ctx: &mut PaintCtx, text: &str, rect: Rect, color: Color) {
+    let layout = ctx
+        .text()
+        .new_text_layout(text.to_string())
+        .font(FontFamily::SYSTEM_UI, FONT_SIZE)
+        .text_color(color)
+        .build()
+        .unwrap();
+
+    let origin = rect.center() - layout.size().to_vec2() / 2.0;
+    ctx.draw_text(&layout, origin);
+}
+
+fn cell_rect(column: usize, y: f64) -> Rect {
+    Rect::from_origin_size((column as f64 * CELL_SIZE, y), (CELL_SIZE, CELL_SIZE))
+}
+
+/// Move the date by whole months. The day is clamped to the length of the
+/// target month.
+fn add_months(date: NaiveDate, months: i32) -> NaiveDate {
+    let month_index = date.year() * 12 + date.month0() as i32 + months;
+    let year = month_index.div_euclid(12);
+    let month = month_index.rem_euclid(12) as u32 + 1;
+
+    (1..=date.day())
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_parse_german_dates() {
+        let expected = NaiveDate::from_ymd(2030, 2, 1);
+
+        assert_eq!(parse_date("01.02.2030"), Ok(expected));
+        assert_eq!(parse_date("1.2.2030"), Ok(expected));
+        assert_eq!(parse_date("01.02.30"), Ok(expected));
+        assert_eq!(parse_date(" 1.2.30. "), Ok(expected));
+    }
+
+    #[test]
+    fn should_parse_iso_dates() {
+        assert_eq!(
+            parse_date("2030-02-01"),
+            Ok(NaiveDate::from_ymd(2030, 2, 1))
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_dates() {
+        assert_eq!(parse_date(""), Err(DateError::Empty));
+        assert_eq!(parse_date("1.2"), Err(DateError::InvalidFormat));
+        assert_eq!(parse_date("1.2.203"), Err(DateError::InvalidFormat));
+        assert_eq!(parse_date("a.b.2030"), Err(DateError::InvalidFormat));
+        assert_eq!(parse_date("30.02.2030"), Err(DateError::InvalidDate));
+    }
+
+    #[test]
+    fn should_clamp_the_day_when_adding_months() {
+        let date = NaiveDate::from_ymd(2030, 1, 31);
+
+        assert_eq!(add_months(date, 1), NaiveDate::from_ymd(2030, 2, 28));
+        assert_eq!(add_months(date, -1), NaiveDate::from_ymd(2029, 12, 31));
+    }
+}
@@ -1,5 +1,7 @@
+use super::AutoFocus;
+use crate::theme;
 use druid::widget::{CrossAxisAlignment, Flex, Label, Maybe, TextBox, Widget, WidgetExt};
-use druid::{theme, Data, Lens};
+use druid::{Data, Lens};
 use std::sync::Arc;
 
 #[derive(Data, Lens, PartialEq, Eq, Clone, Default, Debug)]
@@ -24,17 +26,20 @@ pub fn input(title: &str) -> impl Widget<InputState> {
         .with_child(Label::new(title))
         .with_child(
             TextBox::new()
+                .controller(AutoFocus)
                 .lens(InputState::value)
                 .expand_width()
                 .padding((0.0, 5.0, 0.0, 0.0)),
         )
-        .with_child(
-            Maybe::or_empty(|| {
-                Label::dynamic(|state: &String, _| state.to_string())
-                    .with_text_color(theme::DISABLED_TEXT_COLOR)
-                    .with_text_size(12.0)
-            })
-            .padding((0.0, 5.0, 0.0, 0.0))
-            .lens(InputState::error),
-        )
+        .with_child(error_label())
+}
+
+pub(super) fn error_label() -> impl Widget<InputState> {
+    Maybe::or_empty(|| {
+        Label::dynamic(|state: &String, _| state.to_string())
+            .with_text_color(theme::MUTED_TEXT)
+            .with_text_size(12.0)
+    })
+    .padding((0.0, 5.0, 0.0, 0.0))
+    .lens(InputState::error)
 }
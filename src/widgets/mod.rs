@@ -1,7 +1,25 @@
+mod auto_focus;
+mod badge;
 mod card;
+mod confirm_dialog;
+mod date_input;
 mod input;
 mod outline_button;
+mod overlay;
+mod secret_label;
+mod sidebar_layout;
+mod theme_scope;
+mod virtual_list;
 
+pub use auto_focus::*;
+pub use badge::*;
 pub use card::*;
+pub use confirm_dialog::*;
+pub use date_input::*;
 pub use input::*;
 pub use outline_button::*;
+pub use overlay::*;
+pub use secret_label::*;
+pub use sidebar_layout::*;
+pub use theme_scope::*;
+pub use virtual_list::*;
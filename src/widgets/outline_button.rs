@@ -1,120 +1,334 @@
+use crate::theme;
 use druid::widget::prelude::*;
-use druid::widget::{Click, ControllerHost, Label, LabelText};
-use druid::{theme, Affine, Data, Insets};
+use druid::widget::LabelText;
+use druid::{ArcStr, Color, Data, Insets, KbKey, KeyEvent, Selector, TextLayout};
 
 const LABEL_INSETS: Insets = Insets::uniform_xy(8., 3.);
 
-/// Use two labels internally to avoid re-rendering the text on every change
-/// of `is_hot`.
+const RESIGN_FOCUS: Selector<()> = Selector::new("app.outline_button.resign_focus");
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ButtonVariant {
+    Default,
+    /// The main action of a form, e.g. "Erstellen".
+    Primary,
+    /// Destructive actions, e.g. "Löschen".
+    Danger,
+}
+
+/// What the button does with an event.
+#[derive(PartialEq, Eq, Debug)]
+enum Action {
+    Press,
+    Release { click: bool },
+    Click,
+    ResignFocus,
+    Ignore,
+}
+
+/// Whether the key clicks a focused button.
+fn is_activation_key(key: &KeyEvent) -> bool {
+    key.mods.is_empty() && (key.key == KbKey::Enter || key.key == KbKey::Character(" ".into()))
+}
+
+fn action(event: &Event, is_active: bool, is_hot: bool, is_disabled: bool) -> Action {
+    match event {
+        Event::MouseDown(_) if !is_disabled => Action::Press,
+        Event::MouseUp(_) if is_active => Action::Release {
+            click: is_hot && !is_disabled,
+        },
+        Event::KeyDown(key) if is_activation_key(key) && !is_disabled => Action::Click,
+        // The button can be enabled again, before the command arrives.
+        Event::Command(cmd) if cmd.is(RESIGN_FOCUS) && is_disabled => Action::ResignFocus,
+        _ => Action::Ignore,
+    }
+}
+
+/// Button with an outline, which is filled while the mouse is over it.
+///
+/// The button can be focused with Tab and clicked with Enter or Space. A
+/// disabled button is greyed out, ignores clicks and is skipped by Tab.
 ///
-/// todo: use specialized label widget, which avoids the `Clone` trait for the text
-/// value passed to the button.
+/// Druid does not expose widgets to screen readers yet, so the button has
+/// no accessible role. Its label is the only description.
 pub struct OutlineButton<T> {
-    active_label: Label<T>,
-    inactive_label: Label<T>,
-    label_size: Size,
+    text: LabelText<T>,
+    layout: TextLayout<ArcStr>,
+    variant: ButtonVariant,
+    is_disabled: Option<Box<dyn Fn(&T, &Env) -> bool>>,
+    on_click: Option<Box<dyn Fn(&mut EventCtx, &mut T, &Env)>>,
 }
 
 impl<T: Data> OutlineButton<T> {
-    pub fn new(text: impl Into<LabelText<T>> + Clone) -> OutlineButton<T> {
-        let active_label = Label::new(text.clone()).with_text_color(theme::BACKGROUND_DARK);
-        let inactive_label = Label::new(text).with_text_color(theme::FOREGROUND_LIGHT);
-
+    pub fn new(text: impl Into<LabelText<T>>) -> OutlineButton<T> {
         OutlineButton {
-            active_label,
-            inactive_label,
-            label_size: Size::ZERO,
+            text: text.into(),
+            layout: TextLayout::new(),
+            variant: ButtonVariant::Default,
+            is_disabled: None,
+            on_click: None,
+        }
+    }
+
+    pub fn on_click(mut self, f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static) -> Self {
+        self.on_click = Some(Box::new(f));
+        self
+    }
+
+    pub fn variant(mut self, variant: ButtonVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn primary(self) -> Self {
+        self.variant(ButtonVariant::Primary)
+    }
+
+    pub fn danger(self) -> Self {
+        self.variant(ButtonVariant::Danger)
+    }
+
+    /// Disable the button, while `is_disabled` returns true, e.g. while a
+    /// required field of a form is empty.
+    pub fn disabled_if(mut self, is_disabled: impl Fn(&T, &Env) -> bool + 'static) -> Self {
+        self.is_disabled = Some(Box::new(is_disabled));
+        self
+    }
+
+    fn is_disabled(&self, data: &T, env: &Env) -> bool {
+        match &self.is_disabled {
+            Some(is_disabled) => is_disabled(data, env),
+            None => false,
         }
     }
 
-    pub fn on_click(
-        self,
-        f: impl Fn(&mut EventCtx, &mut T, &Env) + 'static,
-    ) -> ControllerHost<Self, Click<T>> {
-        ControllerHost::new(self, Click::new(f))
+    fn color(&self, env: &Env) -> Color {
+        match self.variant {
+            ButtonVariant::Default => env.get(theme::TEXT),
+            ButtonVariant::Primary => env.get(theme::ACCENT),
+            ButtonVariant::Danger => env.get(theme::DANGER),
+        }
     }
 }
 
 impl<T: Data> Widget<T> for OutlineButton<T> {
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _data: &mut T, _env: &Env) {
-        match event {
-            Event::MouseDown(_) => {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let is_disabled = self.is_disabled(data, env);
+
+        match action(event, ctx.is_active(), ctx.is_hot(), is_disabled) {
+            Action::Press => {
                 ctx.set_active(true);
                 ctx.request_paint();
             }
-            Event::MouseUp(_) => {
-                if ctx.is_active() {
-                    ctx.request_paint();
-                }
+            Action::Release { click } => {
                 ctx.set_active(false);
+                ctx.request_paint();
+                if click {
+                    if let Some(on_click) = &self.on_click {
+                        on_click(ctx, data, env);
+                    }
+                }
             }
-            _ => (),
+            Action::Click => {
+                if let Some(on_click) = &self.on_click {
+                    on_click(ctx, data, env);
+                }
+                ctx.set_handled();
+            }
+            Action::ResignFocus => {
+                if ctx.is_focused() {
+                    ctx.resign_focus();
+                }
+                ctx.set_handled();
+            }
+            Action::Ignore => (),
         }
     }
 
     fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
-        if let LifeCycle::HotChanged(_) = event {
-            ctx.request_paint();
+        match event {
+            LifeCycle::WidgetAdded => {
+                self.text.resolve(data, env);
+                self.layout.set_text(self.text.display_text());
+            }
+            LifeCycle::BuildFocusChain if !self.is_disabled(data, env) => {
+                ctx.register_for_focus();
+            }
+            LifeCycle::HotChanged(_) | LifeCycle::FocusChanged(_) => ctx.request_paint(),
+            _ => (),
         }
-        self.active_label.lifecycle(ctx, event, data, env);
-        self.inactive_label.lifecycle(ctx, event, data, env);
     }
 
     fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
-        self.active_label.update(ctx, old_data, data, env);
-        self.inactive_label.update(ctx, old_data, data, env);
+        if self.text.resolve(data, env) {
+            self.layout.set_text(self.text.display_text());
+            ctx.request_layout();
+        }
+        let is_disabled = self.is_disabled(data, env);
+        if self.is_disabled(old_data, env) != is_disabled {
+            // Rebuild the focus chain, which skips disabled buttons.
+            ctx.children_changed();
+            ctx.request_paint();
+            if is_disabled && ctx.is_focused() {
+                // The focus can only be resigned while handling an event.
+                ctx.submit_command(RESIGN_FOCUS.to(ctx.widget_id()));
+            }
+        }
     }
 
-    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
         let padding = Size::new(LABEL_INSETS.x_value(), LABEL_INSETS.y_value());
-        let label_bc = bc.shrink(padding).loosen();
 
-        self.label_size = self.active_label.layout(ctx, &label_bc, data, env);
-        assert_eq!(
-            self.inactive_label.layout(ctx, &label_bc, data, env),
-            self.label_size
-        );
+        self.layout.rebuild_if_needed(ctx.text(), env);
+        let label_size = self.layout.size();
 
         // HACK: to make sure we look okay at default sizes when beside a textbox,
         // we make sure we will have at least the same height as the default textbox.
-        let min_height = env.get(theme::BORDERED_WIDGET_HEIGHT);
-        let baseline = self.active_label.baseline_offset();
+        let min_height = env.get(druid::theme::BORDERED_WIDGET_HEIGHT);
+        let baseline = label_size.height - self.layout.layout_metrics().first_baseline;
         ctx.set_baseline_offset(baseline + LABEL_INSETS.y1);
 
-        let button_size = bc.constrain(Size::new(
-            self.label_size.width + padding.width,
-            (self.label_size.height + padding.height).max(min_height),
-        ));
-        button_size
+        bc.constrain(Size::new(
+            label_size.width + padding.width,
+            (label_size.height + padding.height).max(min_height),
+        ))
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
-        let is_hot = ctx.is_hot();
+        let is_disabled = self.is_disabled(data, env);
+        let is_hot = ctx.is_hot() && !is_disabled;
         let size = ctx.size();
         let stroke_width = 1.;
 
+        let color = match is_disabled {
+            true => env.get(theme::MUTED_TEXT),
+            false => self.color(env),
+        };
+
         let rounded_rect = size
             .to_rect()
             .inset(-stroke_width / 2.0)
-            .to_rounded_rect(env.get(theme::BUTTON_BORDER_RADIUS));
+            .to_rounded_rect(env.get(druid::theme::BUTTON_BORDER_RADIUS));
 
         if is_hot {
-            ctx.fill(rounded_rect, &env.get(theme::FOREGROUND_LIGHT));
+            ctx.fill(rounded_rect, &color);
+        }
+
+        // The focus ring replaces the border.
+        if ctx.is_focused() && !is_disabled {
+            let focus_width = 2.0;
+            let focus_rect = size
+                .to_rect()
+                .inset(-focus_width / 2.0)
+                .to_rounded_rect(env.get(druid::theme::BUTTON_BORDER_RADIUS));
+            ctx.stroke(focus_rect, &env.get(theme::ACCENT), focus_width);
+        } else {
+            ctx.stroke(rounded_rect, &color, stroke_width);
         }
 
-        let border_color = env.get(theme::FOREGROUND_LIGHT);
-        ctx.stroke(rounded_rect, &border_color, stroke_width);
+        // Only the color changes, so rebuilding keeps the size of the layout.
+        self.layout.set_text_color(match is_hot {
+            true => env.get(theme::BACKGROUND),
+            false => color,
+        });
+        self.layout.rebuild_if_needed(ctx.text(), env);
+
+        let label_offset = (size.to_vec2() - self.layout.size().to_vec2()) / 2.0;
+        self.layout.draw(ctx, label_offset.to_point());
+    }
+}
 
-        let label_offset = (size.to_vec2() - self.label_size.to_vec2()) / 2.0;
+#[cfg(test)]
+mod test {
+    use super::*;
+    use druid::{Modifiers, MouseButton, MouseButtons, MouseEvent, Point, RawMods, Vec2};
 
-        ctx.with_save(|ctx| {
-            ctx.transform(Affine::translate(label_offset));
+    fn mouse() -> MouseEvent {
+        MouseEvent {
+            pos: Point::ORIGIN,
+            window_pos: Point::ORIGIN,
+            buttons: MouseButtons::new(),
+            mods: Modifiers::empty(),
+            count: 1,
+            focus: false,
+            button: MouseButton::Left,
+            wheel_delta: Vec2::ZERO,
+        }
+    }
 
-            if is_hot {
-                self.active_label.paint(ctx, data, env);
-            } else {
-                self.inactive_label.paint(ctx, data, env);
-            }
-        });
+    fn key(mods: RawMods, key: KbKey) -> Event {
+        Event::KeyDown(KeyEvent::for_test(mods, key))
+    }
+
+    #[test]
+    fn should_click_when_the_mouse_is_released_over_the_button() {
+        assert_eq!(
+            action(&Event::MouseDown(mouse()), false, true, false),
+            Action::Press
+        );
+        assert_eq!(
+            action(&Event::MouseUp(mouse()), true, true, false),
+            Action::Release { click: true }
+        );
+        assert_eq!(
+            action(&Event::MouseUp(mouse()), true, false, false),
+            Action::Release { click: false }
+        );
+        assert_eq!(
+            action(&Event::MouseUp(mouse()), false, true, false),
+            Action::Ignore
+        );
+    }
+
+    #[test]
+    fn should_click_with_enter_and_space() {
+        assert_eq!(
+            action(&key(RawMods::None, KbKey::Enter), false, false, false),
+            Action::Click
+        );
+        assert_eq!(
+            action(
+                &key(RawMods::None, KbKey::Character(" ".into())),
+                false,
+                false,
+                false
+            ),
+            Action::Click
+        );
+        assert_eq!(
+            action(&key(RawMods::Ctrl, KbKey::Enter), false, false, false),
+            Action::Ignore
+        );
+        assert_eq!(
+            action(&key(RawMods::None, KbKey::Escape), false, false, false),
+            Action::Ignore
+        );
+    }
+
+    #[test]
+    fn should_ignore_clicks_while_disabled() {
+        assert_eq!(
+            action(&Event::MouseDown(mouse()), false, true, true),
+            Action::Ignore
+        );
+        assert_eq!(
+            action(&Event::MouseUp(mouse()), true, true, true),
+            Action::Release { click: false }
+        );
+        assert_eq!(
+            action(&key(RawMods::None, KbKey::Enter), false, false, true),
+            Action::Ignore
+        );
+    }
+
+    #[test]
+    fn should_resign_the_focus_once_disabled() {
+        let resign_focus = Event::Command(RESIGN_FOCUS.into());
+
+        assert_eq!(
+            action(&resign_focus, false, false, true),
+            Action::ResignFocus
+        );
+        assert_eq!(action(&resign_focus, false, false, false), Action::Ignore);
     }
 }
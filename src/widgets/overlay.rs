@@ -0,0 +1,85 @@
+use crate::theme;
+use druid::widget::prelude::*;
+use druid::{Point, WidgetPod};
+
+const OVERLAY_TOP: f64 = 80.0;
+
+/// Shows `overlay` above `child`, while `is_open` returns true. The child is
+/// dimmed and does not receive mouse events, while the overlay is open.
+pub struct Overlay<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    overlay: WidgetPod<T, Box<dyn Widget<T>>>,
+    is_open: Box<dyn Fn(&T) -> bool>,
+}
+
+impl<T: Data> Overlay<T> {
+    pub fn new(
+        child: impl Widget<T> + 'static,
+        overlay: impl Widget<T> + 'static,
+        is_open: impl Fn(&T) -> bool + 'static,
+    ) -> Overlay<T> {
+        Overlay {
+            child: WidgetPod::new(Box::new(child)),
+            overlay: WidgetPod::new(Box::new(overlay)),
+            is_open: Box::new(is_open),
+        }
+    }
+}
+
+pub(super) fn is_mouse_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_) | Event::Wheel(_)
+    )
+}
+
+impl<T: Data> Widget<T> for Overlay<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let is_open = (self.is_open)(data);
+
+        if is_open || !is_mouse_event(event) {
+            self.overlay.event(ctx, event, data, env);
+        }
+        if !is_open || !is_mouse_event(event) {
+            self.child.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.child.lifecycle(ctx, event, data, env);
+        self.overlay.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &T, data: &T, env: &Env) {
+        if (self.is_open)(old_data) != (self.is_open)(data) {
+            ctx.request_layout();
+        }
+        self.child.update(ctx, data, env);
+        self.overlay.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = self.child.layout(ctx, bc, data, env);
+        self.child.set_origin(ctx, data, env, Point::ORIGIN);
+
+        let overlay_bc = BoxConstraints::new(
+            Size::ZERO,
+            Size::new(size.width, (size.height - OVERLAY_TOP).max(0.0)),
+        );
+        let overlay_size = self.overlay.layout(ctx, &overlay_bc, data, env);
+        let origin = Point::new((size.width - overlay_size.width) / 2.0, OVERLAY_TOP);
+        self.overlay.set_origin(ctx, data, env, origin);
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, env);
+
+        if (self.is_open)(data) {
+            let rect = ctx.size().to_rect();
+            ctx.fill(rect, &env.get(theme::OVERLAY));
+            self.overlay.paint(ctx, data, env);
+        }
+    }
+}
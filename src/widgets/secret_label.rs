@@ -0,0 +1,99 @@
+use druid::widget::prelude::*;
+use druid::widget::Label;
+use druid::Data;
+
+/// Shown instead of a secret value. It has a fixed length to not reveal the
+/// length of the value.
+pub const MASK: &str = "••••••••";
+
+/// Label, which masks its text if it is secret, e.g. while sharing the
+/// screen. The text is revealed while the mouse button is held down on the
+/// label, a double click keeps it revealed until the next double click.
+pub struct SecretLabel<T> {
+    text: Box<dyn Fn(&T, &Env) -> String>,
+    is_secret: Box<dyn Fn(&T, &Env) -> bool>,
+    label: Label<String>,
+    shown_text: String,
+    is_held: bool,
+    is_peeking: bool,
+}
+
+impl<T: Data> SecretLabel<T> {
+    pub fn new(
+        text: impl Fn(&T, &Env) -> String + 'static,
+        is_secret: impl Fn(&T, &Env) -> bool + 'static,
+    ) -> SecretLabel<T> {
+        SecretLabel {
+            text: Box::new(text),
+            is_secret: Box::new(is_secret),
+            label: Label::dynamic(|text: &String, _env| text.clone()),
+            shown_text: String::new(),
+            is_held: false,
+            is_peeking: false,
+        }
+    }
+
+    pub fn with_text_size(mut self, size: f64) -> SecretLabel<T> {
+        self.label.set_text_size(size);
+        self
+    }
+
+    fn resolve(&self, data: &T, env: &Env) -> String {
+        let is_revealed = self.is_held || self.is_peeking;
+
+        match (self.is_secret)(data, env) && !is_revealed {
+            true => String::from(MASK),
+            false => (self.text)(data, env),
+        }
+    }
+
+    /// Request an update, which shows the text, if it has to change.
+    fn refresh(&self, ctx: &mut EventCtx, data: &T, env: &Env) {
+        if self.resolve(data, env) != self.shown_text {
+            ctx.request_update();
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for SecretLabel<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::MouseDown(mouse) => {
+                ctx.set_active(true);
+                self.is_held = true;
+                if mouse.count == 2 {
+                    self.is_peeking = !self.is_peeking;
+                }
+                self.refresh(ctx, data, env);
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                self.is_held = false;
+                self.refresh(ctx, data, env);
+            }
+            _ => (),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            self.shown_text = self.resolve(data, env);
+        }
+        self.label.lifecycle(ctx, event, &self.shown_text, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let text = self.resolve(data, env);
+        let old_text = std::mem::replace(&mut self.shown_text, text);
+
+        self.label.update(ctx, &old_text, &self.shown_text, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
+        self.label.layout(ctx, bc, &self.shown_text, env)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
+        self.label.paint(ctx, &self.shown_text, env);
+    }
+}
@@ -0,0 +1,116 @@
+use super::overlay::is_mouse_event;
+use crate::theme;
+use druid::kurbo::Line;
+use druid::widget::prelude::*;
+use druid::{Point, WidgetPod};
+
+/// Share of the width, which the expanded sidebar takes.
+const SIDEBAR_RATIO: f64 = 0.3;
+const MIN_SIDEBAR_WIDTH: f64 = 150.0;
+
+/// Sidebar next to the content. Below `breakpoint`, the sidebar collapses
+/// into the narrow `rail`, e.g. with icons instead of links.
+pub struct SidebarLayout<T> {
+    sidebar: WidgetPod<T, Box<dyn Widget<T>>>,
+    rail: WidgetPod<T, Box<dyn Widget<T>>>,
+    content: WidgetPod<T, Box<dyn Widget<T>>>,
+    breakpoint: f64,
+    is_collapsed: bool,
+}
+
+impl<T: Data> SidebarLayout<T> {
+    pub fn new(
+        sidebar: impl Widget<T> + 'static,
+        rail: impl Widget<T> + 'static,
+        content: impl Widget<T> + 'static,
+    ) -> SidebarLayout<T> {
+        SidebarLayout {
+            sidebar: WidgetPod::new(Box::new(sidebar)),
+            rail: WidgetPod::new(Box::new(rail)),
+            content: WidgetPod::new(Box::new(content)),
+            breakpoint: 700.0,
+            is_collapsed: false,
+        }
+    }
+
+    pub fn breakpoint(mut self, breakpoint: f64) -> SidebarLayout<T> {
+        self.breakpoint = breakpoint;
+        self
+    }
+
+    fn visible_sidebar(&mut self) -> &mut WidgetPod<T, Box<dyn Widget<T>>> {
+        match self.is_collapsed {
+            true => &mut self.rail,
+            false => &mut self.sidebar,
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for SidebarLayout<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        // The hidden sidebar only misses the mouse, so it keeps reacting
+        // to commands, e.g. for navigation.
+        if is_mouse_event(event) {
+            self.visible_sidebar().event(ctx, event, data, env);
+        } else {
+            self.sidebar.event(ctx, event, data, env);
+            self.rail.event(ctx, event, data, env);
+        }
+        self.content.event(ctx, event, data, env);
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        self.sidebar.lifecycle(ctx, event, data, env);
+        self.rail.lifecycle(ctx, event, data, env);
+        self.content.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.sidebar.update(ctx, data, env);
+        self.rail.update(ctx, data, env);
+        self.content.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = bc.max();
+        self.is_collapsed = size.width < self.breakpoint;
+
+        // The hidden one is laid out as well, so it does not keep
+        // requesting a layout.
+        let sidebar_width = (size.width * SIDEBAR_RATIO).max(MIN_SIDEBAR_WIDTH);
+        let sidebar_bc = BoxConstraints::tight(Size::new(sidebar_width, size.height));
+        let rail_bc = BoxConstraints::new(
+            Size::new(0.0, size.height),
+            Size::new(size.width, size.height),
+        );
+        let sidebar_size = self.sidebar.layout(ctx, &sidebar_bc, data, env);
+        self.sidebar.set_origin(ctx, data, env, Point::ORIGIN);
+        let rail_size = self.rail.layout(ctx, &rail_bc, data, env);
+        self.rail.set_origin(ctx, data, env, Point::ORIGIN);
+
+        let sidebar_size = match self.is_collapsed {
+            true => rail_size,
+            false => sidebar_size,
+        };
+
+        // The divider takes one pixel.
+        let content_origin = Point::new(sidebar_size.width + 1.0, 0.0);
+        let content_size = Size::new((size.width - content_origin.x).max(0.0), size.height);
+        self.content
+            .layout(ctx, &BoxConstraints::tight(content_size), data, env);
+        self.content.set_origin(ctx, data, env, content_origin);
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let sidebar = self.visible_sidebar();
+        sidebar.paint(ctx, data, env);
+
+        let x = sidebar.layout_rect().x1 + 0.5;
+        let line = Line::new((x, 0.0), (x, ctx.size().height));
+        ctx.stroke(line, &env.get(theme::BORDER), 1.0);
+
+        self.content.paint(ctx, data, env);
+    }
+}
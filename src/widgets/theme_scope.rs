@@ -0,0 +1,102 @@
+use crate::data::Theme;
+use crate::theme::{self, Palette};
+use druid::widget::prelude::*;
+use druid::{Point, Selector, TimerToken, WidgetPod};
+use std::time::Duration;
+
+/// Switch the theme of the whole window.
+pub const SET_THEME: Selector<Theme> = Selector::new("app.set_theme");
+
+/// How often the theme of the operating system is checked, while the
+/// system theme is selected.
+const SYSTEM_THEME_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Passes the colors of the selected theme to its child. The theme is
+/// switched with `SET_THEME`, without rebuilding the widgets.
+pub struct ThemeScope<T> {
+    child: WidgetPod<T, Box<dyn Widget<T>>>,
+    theme: Theme,
+    /// The theme, which `Theme::System` currently stands for.
+    resolved: Theme,
+    timer: TimerToken,
+}
+
+impl<T: Data> ThemeScope<T> {
+    pub fn new(theme: Theme, child: impl Widget<T> + 'static) -> ThemeScope<T> {
+        ThemeScope {
+            child: WidgetPod::new(Box::new(child)),
+            theme,
+            resolved: theme::resolve(theme),
+            timer: TimerToken::INVALID,
+        }
+    }
+
+    fn env(&self, env: &Env) -> Env {
+        let mut env = env.clone();
+        Palette::of(self.resolved).apply(&mut env);
+        env.set(theme::THEME, self.theme.id());
+
+        env
+    }
+
+    fn set_resolved(&mut self, ctx: &mut EventCtx, resolved: Theme) {
+        if resolved != self.resolved {
+            self.resolved = resolved;
+            ctx.request_update();
+            ctx.request_layout();
+            ctx.request_paint();
+        }
+    }
+}
+
+impl<T: Data> Widget<T> for ThemeScope<T> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        match event {
+            Event::Command(cmd) if cmd.is(SET_THEME) => {
+                self.theme = *cmd.get_unchecked(SET_THEME);
+                // The id of the theme is part of the env, even if the
+                // colors stay the same.
+                ctx.request_update();
+                self.set_resolved(ctx, theme::resolve(self.theme));
+
+                if self.theme == Theme::System {
+                    self.timer = ctx.request_timer(SYSTEM_THEME_INTERVAL);
+                }
+                ctx.set_handled();
+            }
+            Event::Timer(token) if *token == self.timer => {
+                if self.theme == Theme::System {
+                    self.set_resolved(ctx, theme::resolve(Theme::System));
+                    self.timer = ctx.request_timer(SYSTEM_THEME_INTERVAL);
+                }
+                ctx.set_handled();
+            }
+            _ => self.child.event(ctx, event, data, &self.env(env)),
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        if let LifeCycle::WidgetAdded = event {
+            if self.theme == Theme::System {
+                self.timer = ctx.request_timer(SYSTEM_THEME_INTERVAL);
+            }
+        }
+        self.child.lifecycle(ctx, event, data, &self.env(env));
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        self.child.update(ctx, data, &self.env(env));
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let env = self.env(env);
+        let size = self.child.layout(ctx, bc, data, &env);
+        self.child.set_origin(ctx, data, &env, Point::ORIGIN);
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        self.child.paint(ctx, data, &self.env(env));
+    }
+}
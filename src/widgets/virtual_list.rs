@@ -0,0 +1,479 @@
+use crate::theme;
+use druid::im::Vector;
+use druid::widget::prelude::*;
+use druid::widget::SizedBox;
+use druid::{Point, Rect, Vec2, WidgetPod};
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Rows above and below the visible ones, which are kept, so scrolling by a
+/// few rows does not rebuild them.
+const OVERSCAN: usize = 5;
+const SCROLLBAR_WIDTH: f64 = 6.0;
+const SCROLLBAR_MIN_HEIGHT: f64 = 20.0;
+
+/// Vertical positions of rows with different heights.
+#[derive(Debug, Default, PartialEq)]
+pub struct RowOffsets {
+    /// The top of each row, followed by the bottom of the last row.
+    offsets: Vec<f64>,
+}
+
+impl RowOffsets {
+    pub fn new(heights: impl IntoIterator<Item = f64>) -> RowOffsets {
+        let mut offsets = vec![0.0];
+        for height in heights {
+            offsets.push(offsets[offsets.len() - 1] + height);
+        }
+
+        RowOffsets { offsets }
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len().saturating_sub(1)
+    }
+
+    /// Height of all rows.
+    pub fn height(&self) -> f64 {
+        self.offsets.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn top(&self, index: usize) -> f64 {
+        self.offsets[index]
+    }
+
+    pub fn row_height(&self, index: usize) -> f64 {
+        self.offsets[index + 1] - self.offsets[index]
+    }
+
+    /// The rows, which are at least partly between `top` and
+    /// `top + height`.
+    pub fn visible(&self, top: f64, height: f64) -> Range<usize> {
+        let start = self
+            .offsets
+            .partition_point(|offset| *offset <= top)
+            .saturating_sub(1);
+        let end = self
+            .offsets
+            .partition_point(|offset| *offset < top + height)
+            .min(self.len());
+
+        start.min(end)..end
+    }
+}
+
+/// The header, which sticks to the top of the list, while its section is
+/// scrolled: the last header above `top`, if there is one.
+pub fn sticky_header(headers: &[usize], offsets: &RowOffsets, top: f64) -> Option<usize> {
+    let position = headers.partition_point(|header| offsets.top(*header) < top);
+
+    match position {
+        0 => None,
+        position => Some(headers[position - 1]),
+    }
+}
+
+fn mouse_pos(event: &Event) -> Option<Point> {
+    match event {
+        Event::MouseDown(mouse) | Event::MouseUp(mouse) | Event::MouseMove(mouse) => {
+            Some(mouse.pos)
+        }
+        Event::Wheel(mouse) => Some(mouse.pos),
+        _ => None,
+    }
+}
+
+/// A scrolling list, which only builds the widgets of the visible rows, so
+/// it stays fast with thousands of rows. Rows marked as header stick to the
+/// top, while their section is scrolled.
+///
+/// The rows are derived from the data and read-only, so the row widgets
+/// have to send changes as notifications or commands. The footer is shown
+/// below the rows and scrolls with them.
+pub struct VirtualList<T, R> {
+    rows: Box<dyn Fn(&T) -> Vector<R>>,
+    build_row: Box<dyn Fn() -> Box<dyn Widget<R>>>,
+    row_height: Box<dyn Fn(&R) -> f64>,
+    is_header: Box<dyn Fn(&R) -> bool>,
+    footer: WidgetPod<T, Box<dyn Widget<T>>>,
+    data: Vector<R>,
+    offsets: RowOffsets,
+    /// Indices of the header rows.
+    headers: Vec<usize>,
+    /// Widgets of the visible rows and of the sticky header by row index.
+    children: BTreeMap<usize, WidgetPod<R, Box<dyn Widget<R>>>>,
+    scroll_offset: f64,
+    viewport: Size,
+    footer_height: f64,
+}
+
+impl<T: Data, R: Data> VirtualList<T, R> {
+    pub fn new<W: Widget<R> + 'static>(
+        rows: impl Fn(&T) -> Vector<R> + 'static,
+        build_row: impl Fn() -> W + 'static,
+        row_height: impl Fn(&R) -> f64 + 'static,
+    ) -> VirtualList<T, R> {
+        VirtualList {
+            rows: Box::new(rows),
+            build_row: Box::new(move || Box::new(build_row())),
+            row_height: Box::new(row_height),
+            is_header: Box::new(|_| false),
+            footer: WidgetPod::new(Box::new(SizedBox::empty())),
+            data: Vector::new(),
+            offsets: RowOffsets::default(),
+            headers: Vec::new(),
+            children: BTreeMap::new(),
+            scroll_offset: 0.0,
+            viewport: Size::ZERO,
+            footer_height: 0.0,
+        }
+    }
+
+    /// Rows, for which `is_header` returns true, stick to the top.
+    pub fn sticky_headers(mut self, is_header: impl Fn(&R) -> bool + 'static) -> Self {
+        self.is_header = Box::new(is_header);
+        self
+    }
+
+    pub fn footer(mut self, footer: impl Widget<T> + 'static) -> Self {
+        self.footer = WidgetPod::new(Box::new(footer));
+        self
+    }
+
+    fn set_rows(&mut self, rows: Vector<R>) {
+        self.offsets = RowOffsets::new(rows.iter().map(|row| (self.row_height)(row)));
+        self.headers = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| (self.is_header)(row))
+            .map(|(index, _)| index)
+            .collect();
+        self.data = rows;
+    }
+
+    fn max_scroll_offset(&self) -> f64 {
+        (self.offsets.height() + self.footer_height - self.viewport.height).max(0.0)
+    }
+
+    fn sticky_index(&self) -> Option<usize> {
+        sticky_header(&self.headers, &self.offsets, self.scroll_offset)
+    }
+
+    /// Build the widgets of the rows, which became visible, and drop the
+    /// others. Returns whether the widgets changed.
+    fn update_children(&mut self) -> bool {
+        let visible = self
+            .offsets
+            .visible(self.scroll_offset, self.viewport.height);
+        let start = visible.start.saturating_sub(OVERSCAN);
+        let end = (visible.end + OVERSCAN).min(self.offsets.len());
+        let sticky = self.sticky_index();
+        let is_needed = |index: usize| (start..end).contains(&index) || Some(index) == sticky;
+
+        let count = self.children.len();
+        self.children.retain(|index, _| is_needed(*index));
+        let mut changed = self.children.len() != count;
+
+        for index in (start..end).chain(sticky) {
+            if !self.children.contains_key(&index) {
+                self.children
+                    .insert(index, WidgetPod::new((self.build_row)()));
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    fn scroll_by(&mut self, ctx: &mut EventCtx, delta: f64) {
+        let offset = (self.scroll_offset + delta).clamp(0.0, self.max_scroll_offset());
+
+        if offset != self.scroll_offset {
+            self.scroll_offset = offset;
+            if self.update_children() {
+                ctx.children_changed();
+            }
+            ctx.request_layout();
+            ctx.request_paint();
+        }
+    }
+
+    /// Vertical position of a row in the viewport. The sticky header stays
+    /// at the top, until the next header pushes it away.
+    fn row_y(&self, index: usize, sticky: Option<usize>) -> f64 {
+        let y = self.offsets.top(index) - self.scroll_offset;
+        if Some(index) != sticky {
+            return y;
+        }
+
+        let height = self.offsets.row_height(index);
+        let next_header = self.headers.iter().find(|header| **header > index);
+        match next_header {
+            Some(next) => (self.offsets.top(*next) - self.scroll_offset - height).min(0.0),
+            None => 0.0,
+        }
+    }
+}
+
+impl<T: Data, R: Data> Widget<T> for VirtualList<T, R> {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut T, env: &Env) {
+        let viewport = ctx.size().to_rect();
+        let sticky = self.sticky_index();
+        let sticky_rect = sticky
+            .and_then(|index| self.children.get(&index))
+            .map(|child| child.layout_rect());
+        let is_over_sticky = match (sticky_rect, mouse_pos(event)) {
+            (Some(rect), Some(pos)) => rect.contains(pos),
+            _ => false,
+        };
+
+        for (index, child) in self.children.iter_mut() {
+            // The sticky header covers the rows below it.
+            let is_covered = is_over_sticky && Some(*index) != sticky && !child.has_active();
+            if !child.is_initialized() || is_covered {
+                continue;
+            }
+
+            // Rows outside of the viewport only get the mouse, while they
+            // are hot or active, like in `Scroll`.
+            let force = child.is_hot() || child.has_active();
+            let row = self.data.get(*index);
+            if let (Some(event), Some(row)) =
+                (event.transform_scroll(Vec2::ZERO, viewport, force), row)
+            {
+                let mut row = row.clone();
+                child.event(ctx, &event, &mut row, env);
+            }
+        }
+
+        let force = self.footer.is_hot() || self.footer.has_active();
+        if let Some(event) = event.transform_scroll(Vec2::ZERO, viewport, force) {
+            self.footer.event(ctx, &event, data, env);
+        }
+
+        if let Event::Wheel(mouse) = event {
+            if !ctx.is_handled() {
+                self.scroll_by(ctx, mouse.wheel_delta.y);
+                ctx.set_handled();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, ctx: &mut LifeCycleCtx, event: &LifeCycle, data: &T, env: &Env) {
+        match event {
+            LifeCycle::WidgetAdded => self.set_rows((self.rows)(data)),
+            LifeCycle::Size(size) => {
+                self.viewport = *size;
+                if self.update_children() {
+                    ctx.children_changed();
+                }
+            }
+            _ => {}
+        }
+
+        let is_adding = matches!(event, LifeCycle::WidgetAdded | LifeCycle::Internal(_));
+        for (index, child) in self.children.iter_mut() {
+            if let Some(row) = self.data.get(*index) {
+                if child.is_initialized() || is_adding {
+                    child.lifecycle(ctx, event, row, env);
+                }
+            }
+        }
+        self.footer.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _old_data: &T, data: &T, env: &Env) {
+        let rows = (self.rows)(data);
+        if !rows.same(&self.data) {
+            self.set_rows(rows);
+            self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+            ctx.request_layout();
+        }
+
+        for (index, child) in self.children.iter_mut() {
+            if let Some(row) = self.data.get(*index) {
+                if child.is_initialized() {
+                    child.update(ctx, row, env);
+                }
+            }
+        }
+        if self.update_children() {
+            ctx.children_changed();
+        }
+        self.footer.update(ctx, data, env);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, data: &T, env: &Env) -> Size {
+        let size = bc.max();
+        let width = (size.width - SCROLLBAR_WIDTH).max(0.0);
+
+        let footer_bc = BoxConstraints::new(Size::new(width, 0.0), Size::new(width, f64::INFINITY));
+        self.footer_height = self.footer.layout(ctx, &footer_bc, data, env).height;
+        self.viewport = size;
+        self.scroll_offset = self.scroll_offset.min(self.max_scroll_offset());
+
+        let sticky = self.sticky_index();
+        let origins: Vec<(usize, f64)> = self
+            .children
+            .keys()
+            .map(|index| (*index, self.row_y(*index, sticky)))
+            .collect();
+        for (index, y) in origins {
+            let (child, row) = match (self.children.get_mut(&index), self.data.get(index)) {
+                (Some(child), Some(row)) if child.is_initialized() => (child, row),
+                _ => continue,
+            };
+            let row_size = Size::new(width, self.offsets.row_height(index));
+
+            child.layout(ctx, &BoxConstraints::tight(row_size), row, env);
+            child.set_origin(ctx, row, env, Point::new(0.0, y));
+        }
+
+        let footer_y = self.offsets.height() - self.scroll_offset;
+        self.footer
+            .set_origin(ctx, data, env, Point::new(0.0, footer_y));
+
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &T, env: &Env) {
+        let size = ctx.size();
+        let sticky = self.sticky_index();
+
+        ctx.with_save(|ctx| {
+            ctx.clip(size.to_rect());
+
+            for (index, child) in self.children.iter_mut() {
+                if Some(*index) == sticky || !child.is_initialized() {
+                    continue;
+                }
+                if let Some(row) = self.data.get(*index) {
+                    child.paint(ctx, row, env);
+                }
+            }
+            self.footer.paint(ctx, data, env);
+
+            // The sticky header is painted last, so it covers the rows.
+            if let Some((child, row)) = sticky.and_then(|index| {
+                let child = self.children.get_mut(&index)?;
+                Some((child, self.data.get(index)?))
+            }) {
+                if child.is_initialized() {
+                    let rect = child.layout_rect();
+                    ctx.fill(rect, &env.get(theme::BACKGROUND));
+                    child.paint(ctx, row, env);
+                }
+            }
+        });
+
+        let content_height = self.offsets.height() + self.footer_height;
+        if content_height > size.height {
+            let height = (size.height * size.height / content_height).max(SCROLLBAR_MIN_HEIGHT);
+            let y = self.scroll_offset / self.max_scroll_offset() * (size.height - height);
+            let thumb = Rect::from_origin_size(
+                (size.width - SCROLLBAR_WIDTH, y),
+                (SCROLLBAR_WIDTH, height),
+            );
+            ctx.fill(
+                thumb.to_rounded_rect(SCROLLBAR_WIDTH / 2.0),
+                &env.get(theme::BORDER),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// A list of 1,000 rows with different heights and a header every 25
+    /// rows, in a viewport of 600 pixels.
+    fn list_of_1000_items() -> VirtualList<Vector<usize>, usize> {
+        let mut list = VirtualList::new(
+            |rows: &Vector<usize>| rows.clone(),
+            SizedBox::empty,
+            |row: &usize| 56.0 + (row % 3) as f64 * 8.0,
+        )
+        .sticky_headers(|row: &usize| row % 25 == 0);
+        list.set_rows((0..1000).collect());
+        list.viewport = Size::new(400.0, 600.0);
+
+        list
+    }
+
+    #[test]
+    fn should_find_the_visible_rows() {
+        let offsets = RowOffsets::new(vec![10.0, 20.0, 10.0, 10.0]);
+
+        assert_eq!(offsets.height(), 50.0);
+        assert_eq!(offsets.visible(0.0, 10.0), 0..1);
+        assert_eq!(offsets.visible(15.0, 10.0), 1..2);
+        assert_eq!(offsets.visible(25.0, 10.0), 1..3);
+        assert_eq!(offsets.visible(45.0, 100.0), 3..4);
+        assert_eq!(RowOffsets::new(vec![]).visible(0.0, 100.0), 0..0);
+    }
+
+    #[test]
+    fn should_find_the_sticky_header() {
+        let offsets = RowOffsets::new(vec![10.0; 6]);
+        let headers = vec![0, 3];
+
+        assert_eq!(sticky_header(&headers, &offsets, 0.0), None);
+        assert_eq!(sticky_header(&headers, &offsets, 5.0), Some(0));
+        assert_eq!(sticky_header(&headers, &offsets, 30.0), Some(0));
+        assert_eq!(sticky_header(&headers, &offsets, 35.0), Some(3));
+    }
+
+    #[test]
+    fn should_only_build_the_visible_rows_of_1000_items() {
+        let mut list = list_of_1000_items();
+
+        assert!(list.update_children());
+        assert_eq!(list.children.len(), 10 + OVERSCAN);
+        assert_eq!(list.children.keys().last(), Some(&(10 + OVERSCAN - 1)));
+
+        list.scroll_offset = 30_000.0;
+        assert!(list.update_children());
+        let visible = list.offsets.visible(30_000.0, 600.0);
+        let sticky = list.sticky_index().unwrap();
+        let expected: Vec<usize> = (sticky..=sticky)
+            .chain(visible.start - OVERSCAN..visible.end + OVERSCAN)
+            .collect();
+
+        assert_eq!(
+            list.children.keys().copied().collect::<Vec<usize>>(),
+            expected
+        );
+        assert!(!list.update_children());
+    }
+
+    /// Scroll from the top to the bottom of 1,000 rows in 1,000 frames, each
+    /// with new rows. The timing depends on the machine, so run it with
+    /// `cargo test --release -- --ignored`.
+    #[test]
+    #[ignore]
+    fn bench_scrolling_through_1000_items() {
+        let mut list = list_of_1000_items();
+        let rows: Vector<usize> = (0..1000).collect();
+        let frames = 1000;
+        let step = list.max_scroll_offset() / frames as f64;
+
+        let start = Instant::now();
+        for frame in 0..=frames {
+            let rows = (list.rows)(&rows);
+            list.set_rows(rows);
+            list.scroll_offset = frame as f64 * step;
+            list.update_children();
+
+            assert!(list.children.len() < 25);
+        }
+        let per_frame = start.elapsed() / frames;
+
+        assert!(
+            per_frame < Duration::from_millis(1),
+            "{:?} per frame",
+            per_frame
+        );
+    }
+}